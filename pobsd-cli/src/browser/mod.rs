@@ -0,0 +1,317 @@
+//! Full-screen browser of the database.
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use pobsdlib::{DataBase, FieldKind, Game, Item, QueryResult};
+
+use crate::commands::query::{filter, parse_query};
+
+pub mod ui;
+
+/// Item collections that can be browsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FacetKind {
+    Engine,
+    Tag,
+    Genre,
+}
+
+impl FacetKind {
+    pub const ALL: [FacetKind; 3] = [FacetKind::Engine, FacetKind::Tag, FacetKind::Genre];
+    pub fn title(&self) -> &'static str {
+        match self {
+            FacetKind::Engine => "Engines",
+            FacetKind::Tag => "Tags",
+            FacetKind::Genre => "Genres",
+        }
+    }
+    fn field(&self) -> FieldKind {
+        match self {
+            FacetKind::Engine => FieldKind::Engine,
+            FacetKind::Tag => FieldKind::Tag,
+            FacetKind::Genre => FieldKind::Genre,
+        }
+    }
+    fn next(&self) -> Self {
+        match self {
+            FacetKind::Engine => FacetKind::Tag,
+            FacetKind::Tag => FacetKind::Genre,
+            FacetKind::Genre => FacetKind::Engine,
+        }
+    }
+    fn previous(&self) -> Self {
+        self.next().next()
+    }
+}
+
+/// Pane receiving the key events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Focus {
+    Search,
+    Games,
+    Facets,
+}
+
+/// Action requested by a key which cannot be performed
+/// by the browser itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Open(String),
+    Copy(String),
+}
+
+/// State of the browser.
+pub struct App {
+    database: DataBase,
+    /// text of the search box.
+    pub search: String,
+    /// games matching the search and the facet.
+    pub games: Vec<Game>,
+    /// position of the selected game in games.
+    pub selected: usize,
+    pub focus: Focus,
+    pub facet_kind: FacetKind,
+    /// items of the current facet kind, with their number of games.
+    pub facets: Vec<(String, usize)>,
+    /// position of the selected item in facets.
+    pub facet_selected: usize,
+    /// facet the games are restricted to.
+    pub facet_filter: Option<(FacetKind, String)>,
+    /// message displayed in the status bar.
+    pub status: String,
+}
+
+fn facet_counts(items: QueryResult<&Item>) -> Vec<(String, usize)> {
+    items
+        .items
+        .iter()
+        .map(|item| (item.name.to_string(), item.count()))
+        .collect()
+}
+
+impl App {
+    pub fn new(database: DataBase) -> Self {
+        let mut app = App {
+            database,
+            search: String::new(),
+            games: Vec::new(),
+            selected: 0,
+            focus: Focus::Games,
+            facet_kind: FacetKind::Engine,
+            facets: Vec::new(),
+            facet_selected: 0,
+            facet_filter: None,
+            status: String::new(),
+        };
+        app.load_facets();
+        app.refresh();
+        app
+    }
+    /// Return the selected game, if any.
+    pub fn selected_game(&self) -> Option<&Game> {
+        self.games.get(self.selected)
+    }
+    fn load_facets(&mut self) {
+        self.facets = facet_counts(self.database.items(self.facet_kind.field()));
+        self.facet_selected = 0;
+    }
+    // Apply the search and the facet to the games. The search
+    // uses the syntax of the query command, an invalid query
+    // being searched in the names.
+    fn refresh(&mut self) {
+        let games = match parse_query(&self.search) {
+            Ok(terms) => {
+                self.status.clear();
+                self.database.game_contains_and(filter(&terms)).items
+            }
+            Err(err) => {
+                self.status = err;
+                let terms = vec![("name", self.search.clone())];
+                self.database.game_contains_and(filter(&terms)).items
+            }
+        };
+        let games = match &self.facet_filter {
+            Some((kind, name)) => {
+                let items = self.database.games_by(kind.field(), name);
+                games
+                    .into_iter()
+                    .filter(|game| items.items.iter().any(|item| item.uuid == game.uuid))
+                    .collect()
+            }
+            None => games,
+        };
+        self.games = games;
+        self.selected = self.selected.min(self.games.len().saturating_sub(1));
+    }
+    fn move_selection(&mut self, offset: isize) {
+        let (position, count) = match self.focus {
+            Focus::Facets => (&mut self.facet_selected, self.facets.len()),
+            _ => (&mut self.selected, self.games.len()),
+        };
+        if count > 0 {
+            *position = position.saturating_add_signed(offset).min(count - 1);
+        }
+    }
+    /// Update the state according to the key and return the
+    /// action to perform, if any.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        match self.focus {
+            Focus::Search => match key.code {
+                KeyCode::Char(c) => {
+                    self.search.push(c);
+                    self.refresh();
+                }
+                KeyCode::Backspace => {
+                    self.search.pop();
+                    self.refresh();
+                }
+                KeyCode::Esc => {
+                    self.search.clear();
+                    self.refresh();
+                    self.focus = Focus::Games;
+                }
+                KeyCode::Enter | KeyCode::Down | KeyCode::Tab => self.focus = Focus::Games,
+                _ => {}
+            },
+            Focus::Games | Focus::Facets => match key.code {
+                KeyCode::Char('q') => return Some(Action::Quit),
+                KeyCode::Char('/') => self.focus = Focus::Search,
+                KeyCode::Tab => {
+                    self.focus = if self.focus == Focus::Games {
+                        Focus::Facets
+                    } else {
+                        Focus::Games
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+                KeyCode::PageUp => self.move_selection(-10),
+                KeyCode::PageDown => self.move_selection(10),
+                KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN),
+                KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX),
+                KeyCode::Esc | KeyCode::Char('x') if self.facet_filter.is_some() => {
+                    self.facet_filter = None;
+                    self.refresh();
+                }
+                KeyCode::Esc => return Some(Action::Quit),
+                _ if self.focus == Focus::Facets => self.handle_facet_key(key),
+                _ => return self.handle_game_key(key),
+            },
+        }
+        None
+    }
+    fn handle_facet_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.facet_kind = self.facet_kind.previous();
+                self.load_facets();
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.facet_kind = self.facet_kind.next();
+                self.load_facets();
+            }
+            KeyCode::Enter => {
+                if let Some((name, _)) = self.facets.get(self.facet_selected) {
+                    self.facet_filter = Some((self.facet_kind, name.clone()));
+                    self.selected = 0;
+                    self.refresh();
+                    self.focus = Focus::Games;
+                }
+            }
+            _ => {}
+        }
+    }
+    fn handle_game_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let game = self.selected_game()?;
+        match key.code {
+            // o opens the first store, 1 to 9 the nth one
+            KeyCode::Char(c @ ('o' | '1'..='9')) => {
+                let position = c.to_digit(10).map(|n| n as usize - 1).unwrap_or(0);
+                let store = game
+                    .stores
+                    .as_ref()
+                    .and_then(|stores| stores.get(position))
+                    .cloned();
+                match store {
+                    Some(store) => {
+                        self.status = format!("Opening {}", store);
+                        Some(Action::Open(store))
+                    }
+                    None => {
+                        self.status = "No such store".to_string();
+                        None
+                    }
+                }
+            }
+            KeyCode::Char('y') => {
+                let entry = game.to_string();
+                self.status = format!("Copied {}", game.name);
+                Some(Action::Copy(entry))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_browser {
+    use super::*;
+    use crossterm::event::KeyEvent;
+    use pobsdlib::DataBaseBuilder;
+    fn app() -> App {
+        let data = "Game\tAeternum\nEngine\tFNA\nStore\thttps://a.example.org https://b.example.org\nTags\tindie\nGame\tShuggy\nEngine\tFNA\nTags\tindie, puzzle\nGame\tOther\nEngine\tGodot\n";
+        App::new(DataBaseBuilder::verbatim().build_from_string(data.to_string()))
+    }
+    fn press(app: &mut App, code: KeyCode) -> Option<Action> {
+        app.handle_key(KeyEvent::from(code))
+    }
+    fn names(app: &App) -> Vec<&str> {
+        app.games.iter().map(|game| game.name.as_str()).collect()
+    }
+    #[test]
+    fn search() {
+        let mut app = app();
+        assert_eq!(app.games.len(), 3);
+        press(&mut app, KeyCode::Char('/'));
+        for c in "engine:fna".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(names(&app), vec!["Aeternum", "Shuggy"]);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.games.len(), 3);
+        assert_eq!(app.focus, Focus::Games);
+    }
+    #[test]
+    fn facets() {
+        let mut app = app();
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.facet_kind, FacetKind::Tag);
+        assert_eq!(
+            app.facets,
+            vec![("indie".to_string(), 2), ("puzzle".to_string(), 1)]
+        );
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(names(&app), vec!["Shuggy"]);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.games.len(), 3);
+    }
+    #[test]
+    fn actions() {
+        let mut app = app();
+        assert_eq!(
+            press(&mut app, KeyCode::Char('2')),
+            Some(Action::Open("https://b.example.org".to_string()))
+        );
+        match press(&mut app, KeyCode::Char('y')) {
+            Some(Action::Copy(entry)) => assert!(entry.starts_with("Game\tAeternum\n")),
+            action => panic!("unexpected action {:?}", action),
+        }
+        press(&mut app, KeyCode::Down);
+        assert_eq!(press(&mut app, KeyCode::Char('o')), None);
+        assert_eq!(press(&mut app, KeyCode::Char('q')), Some(Action::Quit));
+    }
+}
@@ -0,0 +1,154 @@
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::Frame;
+
+use crate::browser::{App, FacetKind, Focus};
+
+const HELP: &str =
+    "/ search  Tab facets  Enter apply facet  x clear facet  o/1-9 open store  y copy  q quit";
+
+fn block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if focused {
+        block.border_style(Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        block
+    }
+}
+
+fn highlight() -> Style {
+    Style::default().add_modifier(Modifier::REVERSED)
+}
+
+/// Draw the browser.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [search, body, status] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [facets, games, details] = Layout::horizontal([
+        Constraint::Percentage(25),
+        Constraint::Percentage(35),
+        Constraint::Percentage(40),
+    ])
+    .areas(body);
+
+    let search_box = Paragraph::new(app.search.as_str()).block(block(
+        "Search (engine:fna tag:indie ...)".to_string(),
+        app.focus == Focus::Search,
+    ));
+    frame.render_widget(search_box, search);
+    if app.focus == Focus::Search {
+        frame.set_cursor_position((
+            search.x + 1 + app.search.chars().count() as u16,
+            search.y + 1,
+        ));
+    }
+
+    // facet browser: tabs for the kinds of items and
+    // the list of items of the current kind
+    let [tabs, items] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+        .areas(block("".to_string(), false).inner(facets));
+    frame.render_widget(
+        block("Facets".to_string(), app.focus == Focus::Facets),
+        facets,
+    );
+    let selected_tab = FacetKind::ALL
+        .iter()
+        .position(|kind| *kind == app.facet_kind)
+        .unwrap_or(0);
+    frame.render_widget(
+        Tabs::new(FacetKind::ALL.iter().map(|kind| kind.title()))
+            .select(selected_tab)
+            .highlight_style(highlight()),
+        tabs,
+    );
+    let facet_items: Vec<ListItem> = app
+        .facets
+        .iter()
+        .map(|(name, count)| ListItem::new(format!("{} ({})", name, count)))
+        .collect();
+    let mut facet_state = ListState::default().with_selected(Some(app.facet_selected));
+    frame.render_stateful_widget(
+        List::new(facet_items).highlight_style(highlight()),
+        items,
+        &mut facet_state,
+    );
+
+    let title = match &app.facet_filter {
+        Some((kind, name)) => format!("Games: {} ({} {})", app.games.len(), kind.title(), name),
+        None => format!("Games: {}", app.games.len()),
+    };
+    let game_items: Vec<ListItem> = app
+        .games
+        .iter()
+        .map(|game| ListItem::new(game.name.as_str()))
+        .collect();
+    let mut game_state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(
+        List::new(game_items)
+            .block(block(title, app.focus == Focus::Games))
+            .highlight_style(highlight()),
+        games,
+        &mut game_state,
+    );
+
+    let lines: Vec<Line> = match app.selected_game() {
+        Some(game) => game
+            .field_values()
+            .into_iter()
+            .map(|(key, value)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:8} ", key),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(value.unwrap_or_default()),
+                ])
+            })
+            .collect(),
+        None => vec![Line::from("No game found")],
+    };
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block("Details".to_string(), false))
+            .wrap(Wrap { trim: false }),
+        details,
+    );
+
+    let message = if app.status.is_empty() {
+        HELP
+    } else {
+        app.status.as_str()
+    };
+    frame.render_widget(Paragraph::new(message), status);
+}
+
+#[cfg(test)]
+mod test_ui {
+    use super::*;
+    use pobsdlib::DataBaseBuilder;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    #[test]
+    fn draw_browser() {
+        let data = "Game\tAeternum\nEngine\tFNA\nTags\tindie\n";
+        let app = App::new(DataBaseBuilder::verbatim().build_from_string(data.to_string()));
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(content.contains("Games: 1"));
+        assert!(content.contains("FNA (1)"));
+        assert!(content.contains("Engine   FNA"));
+    }
+}
@@ -0,0 +1,92 @@
+use crossterm::event::{self, Event, KeyEventKind};
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use crate::browser::{ui, Action, App};
+use crate::commands::CommandResult;
+use crate::Context;
+
+// Open the url with $BROWSER, xdg-open otherwise.
+fn open(url: &str) -> io::Result<()> {
+    let browser = std::env::var("BROWSER").unwrap_or_else(|_| "xdg-open".to_string());
+    Command::new(browser)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+// Copy the text to the clipboard using the OSC 52 escape
+// sequence, which works in the console through tmux as
+// well as in most terminal emulators.
+fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()
+}
+
+fn browse(app: &mut App) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    loop {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match app.handle_key(key) {
+                Some(Action::Quit) => return Ok(()),
+                Some(Action::Open(url)) => {
+                    if let Err(err) = open(&url) {
+                        app.status = format!("Could not open {}: {}", url, err);
+                    }
+                }
+                Some(Action::Copy(entry)) => copy(&entry)?,
+                None => {}
+            }
+        }
+    }
+}
+
+pub fn run(context: &Context) -> CommandResult {
+    let mut app = App::new(context.source.load()?);
+    let result = browse(&mut app);
+    ratatui::restore();
+    result.map_err(|err| format!("Terminal error: {}", err))?;
+    Ok(0)
+}
+
+#[cfg(test)]
+mod test_browse {
+    use super::*;
+    #[test]
+    fn encode() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"G"), "Rw==");
+        assert_eq!(base64(b"Ga"), "R2E=");
+        assert_eq!(base64(b"Game"), "R2FtZQ==");
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::Local;
+use clap::Args;
+use pobsdlib::utils::{bump_updated, git_show};
+use std::fs;
+use std::path::Path;
+
+use crate::commands::CommandResult;
+use crate::source::Source;
+use crate::Context;
+
+#[derive(Args, Debug)]
+pub struct BumpArgs {
+    /// Base version of the database
+    #[arg(long, required_unless_present = "rev", conflicts_with = "rev")]
+    pub base: Option<String>,
+    /// Git revision of the base version, read from the
+    /// repository holding the database file
+    #[arg(long)]
+    pub rev: Option<String>,
+    /// List the games to update without writing the database
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+pub fn run(args: BumpArgs, context: &Context) -> CommandResult {
+    let path = match &context.source {
+        Source::Path(path) => path,
+        _ => return Err("pobsd bump requires a database file".to_string()),
+    };
+    let data = context.source.read_text()?;
+    let base = match (&args.base, &args.rev) {
+        (Some(base), _) => Source::parse(base).read_text()?,
+        (None, Some(rev)) => {
+            let path = Path::new(path);
+            let repo = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            let file = path
+                .file_name()
+                .map(|name| format!("./{}", name.to_string_lossy()))
+                .unwrap_or_default();
+            git_show(repo, rev, &file)
+                .map_err(|err| format!("Could not read {} at {}: {}", file, rev, err))?
+        }
+        (None, None) => unreachable!("clap requires --base or --rev"),
+    };
+    let result = bump_updated(&base, &data, Local::now().date_naive());
+    for name in &result.games {
+        println!("{}", name);
+    }
+    if !args.dry_run && result.content != data {
+        fs::write(path, result.content)
+            .map_err(|err| format!("Could not write {}: {}", path, err))?;
+    }
+    Ok(0)
+}
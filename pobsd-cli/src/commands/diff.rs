@@ -0,0 +1,63 @@
+use clap::Args;
+use pobsdlib::utils::diff_databases;
+
+use crate::commands::CommandResult;
+use crate::output::{to_json, OutputFormat};
+use crate::source::Source;
+use crate::Context;
+
+// Can be used by git either as an external diff tool
+// or as a textconv filter:
+//
+// .gitattributes
+//     openbsd-games.db diff=pobsd
+// .git/config
+//     [diff "pobsd"]
+//         command = pobsd diff
+// or
+//     [diff "pobsd"]
+//         textconv = pobsd diff --textconv
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Print the games of the database sorted by name (git textconv filter)
+    #[arg(long, value_name = "DATABASE", conflicts_with = "databases")]
+    pub textconv: Option<String>,
+    /// Old database (a path, an http(s) url or - for stdin) and new
+    /// database (the --db database by default), or the seven
+    /// arguments given by git to an external diff tool
+    #[arg(num_args = 1..=7, required_unless_present = "textconv")]
+    pub databases: Vec<String>,
+}
+
+pub fn run(args: DiffArgs, context: &Context) -> CommandResult {
+    if let Some(textconv) = &args.textconv {
+        // sorted by name so that reordering the database
+        // does not show up in the diff
+        let mut games = Source::parse(textconv).load()?.get_all_games().items;
+        games.sort_by_key(|game| game.name.to_lowercase());
+        for game in games {
+            println!("{}", game);
+        }
+        return Ok(0);
+    }
+    let (old, new) = match args.databases.as_slice() {
+        [old] => (Source::parse(old).load()?, context.source.load()?),
+        [old, new] => (Source::parse(old).load()?, Source::parse(new).load()?),
+        // called by git as GIT_EXTERNAL_DIFF
+        [path, old, _, _, new, _, _] => {
+            println!("diff {}", path);
+            (Source::parse(old).load()?, Source::parse(new).load()?)
+        }
+        _ => return Err("Expected OLD [NEW] or the 7 arguments of git".to_string()),
+    };
+    let changes = diff_databases(&old, &new);
+    match context.output {
+        OutputFormat::Json => print!("{}", to_json(&changes)),
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Db => {
+            for change in &changes {
+                println!("{}", change);
+            }
+        }
+    }
+    Ok(0)
+}
@@ -0,0 +1,161 @@
+use clap::{Args, ValueEnum};
+use pobsdlib::utils::{write_facets, write_game_tree, CsvOptions};
+use pobsdlib::{DataBase, FieldKind, Item};
+use std::fs;
+use std::path::Path;
+
+use crate::commands::CommandResult;
+use crate::output::{parse_fields, render_csv, render_games, OutputFormat, Table, Terminal};
+use crate::Context;
+
+/// Collections that can be exported with the number of
+/// games of each of their items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Facet {
+    Engines,
+    Runtimes,
+    Genres,
+    Tags,
+    Years,
+    Devs,
+    Publishers,
+    Stores,
+    Statuses,
+    Setups,
+}
+
+impl Facet {
+    fn kind(self) -> FieldKind {
+        match self {
+            Facet::Engines => FieldKind::Engine,
+            Facet::Runtimes => FieldKind::Runtime,
+            Facet::Genres => FieldKind::Genre,
+            Facet::Tags => FieldKind::Tag,
+            Facet::Years => FieldKind::Year,
+            Facet::Devs => FieldKind::Dev,
+            Facet::Publishers => FieldKind::Publi,
+            Facet::Stores => FieldKind::Store,
+            Facet::Statuses => FieldKind::Status,
+            Facet::Setups => FieldKind::Setup,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Write the export to the given file instead of stdout
+    #[arg(long)]
+    pub to: Option<String>,
+    /// Comma separated list of the fields to export (csv and tsv only)
+    #[arg(long, conflicts_with = "facet")]
+    pub fields: Option<String>,
+    /// Separator joining stores, genres and tags (csv and tsv only)
+    #[arg(long, default_value = ", ")]
+    pub list_separator: String,
+    /// Export the items of a collection with their number of games
+    /// instead of the games
+    #[arg(long, value_enum)]
+    pub facet: Option<Facet>,
+    /// Write the database to the given SQLite file (replaced if it exists)
+    #[arg(long, conflicts_with_all = ["to", "fields", "facet"])]
+    pub sqlite: Option<String>,
+    /// Write each game in its own TOML file in the given directory
+    /// (one subdirectory per first letter), which can be read back with --db
+    #[arg(long, conflicts_with_all = ["to", "fields", "facet", "sqlite"])]
+    pub tree: Option<String>,
+}
+
+fn csv_options(format: OutputFormat) -> Option<CsvOptions> {
+    match format {
+        OutputFormat::Csv => Some(CsvOptions::csv()),
+        OutputFormat::Tsv => Some(CsvOptions::tsv()),
+        _ => None,
+    }
+}
+
+fn render_facet(
+    items: &[&Item],
+    format: OutputFormat,
+    terminal: &Terminal,
+) -> Result<String, String> {
+    match (format, csv_options(format)) {
+        (_, Some(options)) => {
+            let mut output: Vec<u8> = Vec::new();
+            write_facets(items, &options, &mut output).map_err(|err| err.to_string())?;
+            String::from_utf8(output).map_err(|err| err.to_string())
+        }
+        (OutputFormat::Table, None) => {
+            let mut table = Table::new(&["NAME", "GAMES"]);
+            for item in items {
+                table.push(vec![item.name.to_string(), item.count().to_string()]);
+            }
+            Ok(table.render(terminal))
+        }
+        _ => Err("--facet is only available with the table, csv and tsv outputs".to_string()),
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn export_sqlite(database: &DataBase, path: &str) -> CommandResult {
+    if std::path::Path::new(path).exists() {
+        fs::remove_file(path).map_err(|err| format!("Could not replace {}: {}", path, err))?;
+    }
+    database
+        .to_sqlite(path)
+        .map_err(|err| format!("Could not write {}: {}", path, err))?;
+    Ok(0)
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn export_sqlite(_: &DataBase, _: &str) -> CommandResult {
+    Err("pobsd was built without the sqlite feature".to_string())
+}
+
+pub fn run(args: ExportArgs, context: &Context) -> CommandResult {
+    let fields = match &args.fields {
+        Some(_) if csv_options(context.output).is_none() => {
+            return Err("--fields is only available with the csv and tsv outputs".to_string())
+        }
+        Some(fields) => Some(parse_fields(fields)?),
+        None => None,
+    };
+    let mut database = context.source.load()?;
+    if let Some(facet) = args.facet {
+        database.register_facet(facet.kind());
+    }
+    if let Some(path) = &args.sqlite {
+        return export_sqlite(&database, path);
+    }
+    if let Some(dir) = &args.tree {
+        write_game_tree(&database, Path::new(dir))
+            .map_err(|err| format!("Could not write {}: {}", dir, err))?;
+        return Ok(0);
+    }
+    let games = database.get_all_games().items;
+    let render = |terminal: &Terminal| -> Result<String, String> {
+        if let Some(facet) = args.facet {
+            return render_facet(
+                &database.items(facet.kind()).items,
+                context.output,
+                terminal,
+            );
+        }
+        match (context.output, csv_options(context.output)) {
+            (_, Some(mut options)) => {
+                options.set_list_separator(&args.list_separator);
+                render_csv(&games, &mut options, fields.as_deref())
+            }
+            // the JSON export can be read back with --db
+            (OutputFormat::Json, None) => Ok(database.to_json() + "\n"),
+            (format, None) => Ok(render_games(&games, format, None, terminal)),
+        }
+    };
+    match &args.to {
+        Some(path) => {
+            let content = render(&Terminal::plain())?;
+            fs::write(path, content).map_err(|err| format!("Could not write {}: {}", path, err))?
+        }
+        None => print!("{}", render(&context.terminal)?),
+    }
+    Ok(0)
+}
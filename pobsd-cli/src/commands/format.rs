@@ -0,0 +1,49 @@
+use clap::Args;
+use pobsdlib::utils::{format_database, format_diff};
+use std::fs;
+
+use crate::commands::CommandResult;
+use crate::source::Source;
+use crate::Context;
+
+#[derive(Args, Debug)]
+pub struct FormatArgs {
+    /// Print the differences and exit with 1 if the database is not formatted
+    #[arg(long, conflicts_with = "in_place")]
+    pub check: bool,
+    /// Rewrite the database file
+    #[arg(long)]
+    pub in_place: bool,
+}
+
+pub fn run(args: FormatArgs, context: &Context) -> CommandResult {
+    let data = context.source.read()?;
+    let formatted = match format_database(&data) {
+        Ok(formatted) => formatted,
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error);
+            }
+            return Err("The database cannot be formatted without losing data".to_string());
+        }
+    };
+    if args.check {
+        if formatted != data {
+            print!("{}", format_diff(&data, &formatted));
+            eprintln!("The database is not formatted");
+            return Ok(1);
+        }
+    } else if args.in_place {
+        let path = match &context.source {
+            Source::Path(path) => path,
+            _ => return Err("--in-place requires a database file".to_string()),
+        };
+        if formatted != data {
+            fs::write(path, formatted)
+                .map_err(|err| format!("Could not write {}: {}", path, err))?;
+        }
+    } else {
+        print!("{}", formatted);
+    }
+    Ok(0)
+}
@@ -0,0 +1,56 @@
+use clap::Args;
+use pobsdlib::models::Severity;
+use pobsdlib::utils::lint_database;
+use pobsdlib::{LintConfig, Vocabulary};
+
+use crate::commands::CommandResult;
+use crate::output::{to_json, OutputFormat};
+use crate::source::Source;
+use crate::Context;
+
+#[derive(Args, Debug)]
+pub struct LintArgs {
+    /// TOML file setting the severity of the rules and the vocabulary
+    #[arg(long)]
+    pub config: Option<String>,
+    /// TOML file giving the known tags and genres, replacing the
+    /// vocabulary of the configuration
+    #[arg(long)]
+    pub vocabulary: Option<String>,
+    /// Print the diagnostics as GitHub workflow annotations
+    #[arg(long)]
+    pub github: bool,
+}
+
+pub fn run(args: LintArgs, context: &Context) -> CommandResult {
+    let mut config = match &args.config {
+        Some(path) => LintConfig::from_file(path)
+            .map_err(|err| format!("Could not read the configuration {}: {}", path, err))?,
+        None => LintConfig::new(),
+    };
+    if let Some(path) = &args.vocabulary {
+        config.vocabulary = Vocabulary::from_file(path)
+            .map_err(|err| format!("Could not read the vocabulary {}: {}", path, err))?;
+    }
+    let filename = match &context.source {
+        Source::Path(path) => path.as_str(),
+        Source::Url(url) => url.as_str(),
+        Source::Stdin => "-",
+    };
+    let diagnostics = lint_database(&context.source.read()?, &config);
+    if args.github {
+        for diagnostic in &diagnostics {
+            println!("{}", diagnostic.to_github_annotation(filename));
+        }
+    } else if context.output == OutputFormat::Json {
+        print!("{}", to_json(&diagnostics));
+    } else {
+        for diagnostic in &diagnostics {
+            println!("{}:{}", filename, diagnostic);
+        }
+    }
+    let failed = diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error);
+    Ok(if failed { 1 } else { 0 })
+}
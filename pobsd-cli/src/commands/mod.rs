@@ -0,0 +1,14 @@
+pub mod browse;
+pub mod bump;
+pub mod diff;
+pub mod export;
+pub mod format;
+pub mod lint;
+pub mod new;
+pub mod query;
+pub mod show;
+pub mod stats;
+
+/// Exit code of the command, or the error to report
+/// (exit code 2).
+pub type CommandResult = Result<i32, String>;
@@ -0,0 +1,151 @@
+use chrono::Local;
+use clap::Args;
+use pobsdlib::models::{Severity, Store};
+use pobsdlib::utils::{get_steam_cover, insert_game, lint_database, similar_names};
+use pobsdlib::{DataBaseBuilder, Game, LintConfig, Symbol};
+use std::fs;
+
+use crate::commands::CommandResult;
+use crate::source::Source;
+use crate::Context;
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// Name of the game
+    pub name: String,
+    /// Store url (can be repeated), the cover being taken from Steam if possible
+    #[arg(long)]
+    pub store: Vec<String>,
+    #[arg(long)]
+    pub cover: Option<String>,
+    #[arg(long)]
+    pub engine: Option<String>,
+    #[arg(long)]
+    pub setup: Option<String>,
+    #[arg(long)]
+    pub runtime: Option<String>,
+    #[arg(long)]
+    pub hints: Option<String>,
+    /// Genre (can be repeated or comma separated)
+    #[arg(long, value_delimiter = ',')]
+    pub genre: Vec<String>,
+    /// Tag (can be repeated or comma separated)
+    #[arg(long, value_delimiter = ',')]
+    pub tag: Vec<String>,
+    #[arg(long)]
+    pub year: Option<String>,
+    #[arg(long)]
+    pub dev: Option<String>,
+    #[arg(long = "pub")]
+    pub publi: Option<String>,
+    #[arg(long)]
+    pub version: Option<String>,
+    #[arg(long)]
+    pub status: Option<String>,
+    /// TOML file setting the severity of the lint rules and the vocabulary
+    #[arg(long)]
+    pub config: Option<String>,
+    /// Print the entry instead of inserting it in the database
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+fn list(items: Vec<String>) -> Option<Vec<String>> {
+    let items: Vec<String> = items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+    if items.is_empty() {
+        None
+    } else {
+        Some(items)
+    }
+}
+
+fn symbols(items: Option<Vec<String>>) -> Option<Vec<Symbol>> {
+    items.map(|items| items.into_iter().map(Symbol::from).collect())
+}
+
+fn build_game(args: NewArgs) -> Game {
+    let today = Local::now().date_naive();
+    let cover = args
+        .cover
+        .or_else(|| args.store.iter().find_map(|store| get_steam_cover(store)));
+    Game {
+        name: args.name.trim().to_string(),
+        cover,
+        engine: args.engine.map(Symbol::from),
+        setup: args.setup,
+        runtime: args.runtime.map(Symbol::from),
+        stores: list(args.store),
+        hints: args.hints,
+        genres: symbols(list(args.genre)),
+        tags: symbols(list(args.tag)),
+        year: args.year.map(Symbol::from),
+        dev: args.dev.map(Symbol::from),
+        publi: args.publi.map(Symbol::from),
+        version: args.version,
+        status: args.status,
+        added: Some(today),
+        updated: Some(today),
+        ..Default::default()
+    }
+}
+
+pub fn run(args: NewArgs, context: &Context) -> CommandResult {
+    let config = match &args.config {
+        Some(path) => LintConfig::from_file(path)
+            .map_err(|err| format!("Could not read the configuration {}: {}", path, err))?,
+        None => LintConfig::new(),
+    };
+    let path = match &context.source {
+        Source::Path(path) => path.clone(),
+        _ if args.dry_run => String::new(),
+        _ => return Err("pobsd new requires a database file".to_string()),
+    };
+    let dry_run = args.dry_run;
+    let game = build_game(args);
+    if let Some(stores) = &game.stores {
+        for store in stores {
+            eprintln!("Store ({}): {}", Store::new(store).kind(), store);
+        }
+    }
+    // the entry is checked alone, the name being
+    // checked against the database below
+    let entry = format!("{}\n", game);
+    let diagnostics = lint_database(&entry, &config);
+    for diagnostic in &diagnostics {
+        eprintln!("{}", diagnostic);
+    }
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        return Ok(1);
+    }
+    let data = context.source.read_text()?;
+    let database = DataBaseBuilder::verbatim().build_from_string(data.clone());
+    let similar = similar_names(&database, &game.name);
+    // names differing only by their case are the same game
+    if let Some(name) = similar
+        .iter()
+        .find(|name| name.to_lowercase() == game.name.to_lowercase())
+    {
+        return Err(format!(
+            "{} is already in the database as {}",
+            game.name, name
+        ));
+    }
+    for name in &similar {
+        eprintln!("Warning: {} looks like {}", game.name, name);
+    }
+    if dry_run {
+        print!("{}", entry);
+    } else {
+        fs::write(&path, insert_game(&data, &game))
+            .map_err(|err| format!("Could not write {}: {}", path, err))?;
+        eprintln!("{} added to {}", game.name, path);
+    }
+    Ok(0)
+}
@@ -0,0 +1,198 @@
+use clap::Args;
+use pobsdlib::{Game, GameFilter};
+
+use crate::commands::CommandResult;
+use crate::output::{game_field, parse_fields, render_games, GAME_FIELDS};
+use crate::Context;
+
+// Keys usable in a query.
+const QUERY_KEYS: [&str; 8] = [
+    "name", "engine", "runtime", "genre", "tag", "year", "dev", "pub",
+];
+
+#[derive(Args, Debug)]
+pub struct QueryArgs {
+    /// Query such as 'engine:fna tag:indie', words without key
+    /// being searched in the name. Keys: name, engine, runtime,
+    /// genre, tag, year, dev, pub
+    pub query: Vec<String>,
+    /// Match any of the criteria instead of all of them
+    #[arg(long)]
+    pub or: bool,
+    /// Sort the games on the given field instead of their position
+    #[arg(long)]
+    pub sort: Option<String>,
+    /// Reverse the order of the games
+    #[arg(long)]
+    pub reverse: bool,
+    /// Comma separated list of the fields to display (e.g. name,year,engine)
+    #[arg(long)]
+    pub fields: Option<String>,
+}
+
+// Split the query in words, double quotes allowing to
+// keep spaces in a value (e.g. dev:"Smudged Cat").
+fn split_words(query: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(word);
+                    word = String::new();
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Parse the query into a value for each key, the
+/// words without key being gathered as the name.
+pub fn parse_query(query: &str) -> Result<Vec<(&'static str, String)>, String> {
+    let mut terms: Vec<(&'static str, String)> = Vec::new();
+    let mut name: Vec<String> = Vec::new();
+    for word in split_words(query) {
+        let (key, value) = match word.split_once(':') {
+            Some((key, value)) => match QUERY_KEYS.iter().find(|k| **k == key.to_lowercase()) {
+                Some(key) => (*key, value.to_string()),
+                None => {
+                    return Err(format!(
+                        "Unknown key: {} (expected one of {})",
+                        key,
+                        QUERY_KEYS.join(", ")
+                    ))
+                }
+            },
+            None => {
+                name.push(word);
+                continue;
+            }
+        };
+        if terms.iter().any(|(k, _)| *k == key) {
+            return Err(format!("{} given twice", key));
+        }
+        terms.push((key, value));
+    }
+    if !name.is_empty() {
+        if terms.iter().any(|(k, _)| *k == "name") {
+            return Err("name given twice".to_string());
+        }
+        terms.push(("name", name.join(" ")));
+    }
+    Ok(terms)
+}
+
+/// Build the filter corresponding to the terms of a query.
+pub fn filter<'a>(terms: &'a [(&'static str, String)]) -> GameFilter<'a> {
+    let mut filter = GameFilter::new();
+    for (key, value) in terms {
+        match *key {
+            "name" => filter.name_contains(value),
+            "engine" => filter.engine_contains(value),
+            "runtime" => filter.runtime_contains(value),
+            "genre" => filter.genre_contains(value),
+            "tag" => filter.tag_contains(value),
+            "year" => filter.year_contains(value),
+            "dev" => filter.dev_contains(value),
+            _ => filter.publi_contains(value),
+        }
+    }
+    filter
+}
+
+// Games without value for the field come last, whatever
+// the direction of the sort.
+fn sort_games(games: &mut [Game], field: &str, reverse: bool) {
+    games.sort_by(|a, b| {
+        let (a, b) = (game_field(a, field), game_field(b, field));
+        match (a, b) {
+            (Some(a), Some(b)) => {
+                let ordering = if field == "id" {
+                    a.parse::<usize>().ok().cmp(&b.parse::<usize>().ok())
+                } else {
+                    a.to_lowercase().cmp(&b.to_lowercase())
+                };
+                if reverse {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        }
+    });
+}
+
+pub fn run(args: QueryArgs, context: &Context) -> CommandResult {
+    let terms = parse_query(&args.query.join(" "))?;
+    let fields = match &args.fields {
+        Some(fields) => Some(parse_fields(fields)?),
+        None => None,
+    };
+    let sort = match &args.sort {
+        Some(sort) if !GAME_FIELDS.contains(&sort.to_lowercase().as_str()) => {
+            return Err(format!(
+                "Unknown field: {} (expected one of {})",
+                sort,
+                GAME_FIELDS.join(", ")
+            ))
+        }
+        Some(sort) => sort.to_lowercase(),
+        None => "id".to_string(),
+    };
+    let database = context.source.load()?;
+    let mut games = if args.or {
+        database.game_contains_or(filter(&terms)).items
+    } else {
+        database.game_contains_and(filter(&terms)).items
+    };
+    sort_games(&mut games, &sort, args.reverse);
+    print!(
+        "{}",
+        render_games(&games, context.output, fields.as_deref(), &context.terminal)
+    );
+    Ok(0)
+}
+
+#[cfg(test)]
+mod test_query {
+    use super::*;
+    use pobsdlib::Symbol;
+    #[test]
+    fn parse() {
+        assert_eq!(
+            parse_query("engine:fna Tag:indie dev:\"Smudged Cat\" shuggy adventures").unwrap(),
+            vec![
+                ("engine", "fna".to_string()),
+                ("tag", "indie".to_string()),
+                ("dev", "Smudged Cat".to_string()),
+                ("name", "shuggy adventures".to_string()),
+            ]
+        );
+        assert!(parse_query("colour:red").is_err());
+        assert!(parse_query("tag:indie tag:free").is_err());
+    }
+    #[test]
+    fn sort() {
+        let game = |id: usize, year: Option<&str>| Game {
+            id,
+            year: year.map(Symbol::from),
+            ..Default::default()
+        };
+        let mut games = vec![game(1, Some("2012")), game(2, None), game(3, Some("2011"))];
+        sort_games(&mut games, "year", false);
+        let ids: Vec<usize> = games.iter().map(|game| game.id).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+        sort_games(&mut games, "year", true);
+        let ids: Vec<usize> = games.iter().map(|game| game.id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+    }
+}
@@ -0,0 +1,39 @@
+use clap::Args;
+
+use crate::commands::CommandResult;
+use crate::output::{render_games, to_json, OutputFormat, Table};
+use crate::Context;
+
+#[derive(Args, Debug)]
+pub struct ShowArgs {
+    /// Name of the game, or its position in the database
+    pub game: String,
+}
+
+pub fn run(args: ShowArgs, context: &Context) -> CommandResult {
+    let database = context.source.load()?;
+    let games = database.get_all_games().items;
+    let game = match args.game.parse::<usize>() {
+        Ok(id) => games.into_iter().find(|game| game.id == id),
+        Err(_) => games.into_iter().find(|game| game.name == args.game),
+    };
+    let game = match game {
+        Some(game) => game,
+        None => return Err(format!("No game found: {}", args.game)),
+    };
+    match context.output {
+        OutputFormat::Table => {
+            let mut table = Table::new(&["", ""]);
+            for (key, value) in game.field_values() {
+                table.push(vec![key.to_string(), value.unwrap_or_default()]);
+            }
+            print!("{}", table.render(&context.terminal));
+        }
+        OutputFormat::Json => print!("{}", to_json(&game)),
+        format @ (OutputFormat::Csv | OutputFormat::Tsv) => {
+            print!("{}", render_games(&[game], format, None, &context.terminal))
+        }
+        OutputFormat::Db => println!("{}", game),
+    }
+    Ok(0)
+}
@@ -0,0 +1,105 @@
+use pobsdlib::{DataBase, Item, QueryResult};
+use serde::Serialize;
+
+use crate::commands::CommandResult;
+use crate::output::{render_rows, to_json, OutputFormat, Table};
+use crate::Context;
+
+// Number of items listed for each collection.
+const TOP: usize = 10;
+
+#[derive(Serialize, Debug)]
+struct Count {
+    name: String,
+    games: usize,
+}
+
+#[derive(Serialize, Debug)]
+struct Collection {
+    name: &'static str,
+    count: usize,
+    top: Vec<Count>,
+}
+
+#[derive(Serialize, Debug)]
+struct Stats {
+    games: usize,
+    collections: Vec<Collection>,
+}
+
+fn collection(name: &'static str, items: QueryResult<&Item>) -> Collection {
+    let mut top: Vec<Count> = items
+        .items
+        .iter()
+        .map(|item| Count {
+            name: item.name.to_string(),
+            games: item.count(),
+        })
+        .collect();
+    // the items are sorted by name, the sort being stable
+    // items with the same count stay in alphabetical order
+    top.sort_by_key(|count| std::cmp::Reverse(count.games));
+    top.truncate(TOP);
+    Collection {
+        name,
+        count: items.count,
+        top,
+    }
+}
+
+fn stats(database: &DataBase) -> Stats {
+    Stats {
+        games: database.get_all_games().count,
+        collections: vec![
+            collection("engines", database.get_all_engines()),
+            collection("runtimes", database.get_all_runtimes()),
+            collection("genres", database.get_all_genres()),
+            collection("tags", database.get_all_tags()),
+            collection("years", database.get_all_years()),
+            collection("devs", database.get_all_devs()),
+            collection("publishers", database.get_all_publis()),
+        ],
+    }
+}
+
+pub fn run(context: &Context) -> CommandResult {
+    let stats = stats(&context.source.load()?);
+    match context.output {
+        OutputFormat::Json => print!("{}", to_json(&stats)),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            for collection in &stats.collections {
+                for count in &collection.top {
+                    rows.push(vec![
+                        collection.name.to_string(),
+                        count.name.clone(),
+                        count.games.to_string(),
+                    ]);
+                }
+            }
+            print!(
+                "{}",
+                render_rows(&["collection", "name", "games"], &rows, context.output)
+            );
+        }
+        OutputFormat::Table | OutputFormat::Db => {
+            let mut table = Table::new(&["COLLECTION", "COUNT"]);
+            table.push(vec!["games".to_string(), stats.games.to_string()]);
+            for collection in &stats.collections {
+                table.push(vec![
+                    collection.name.to_string(),
+                    collection.count.to_string(),
+                ]);
+            }
+            print!("{}", table.render(&context.terminal));
+            for collection in &stats.collections {
+                let mut table = Table::new(&[&collection.name.to_uppercase(), "GAMES"]);
+                for count in &collection.top {
+                    table.push(vec![count.name.clone(), count.games.to_string()]);
+                }
+                print!("\n{}", table.render(&context.terminal));
+            }
+        }
+    }
+    Ok(0)
+}
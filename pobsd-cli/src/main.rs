@@ -0,0 +1,104 @@
+//! # pobsd
+//! Command-line tool to interact with the PlayOnBSD
+//! database, built on pobsdlib.
+use clap::{Parser, Subcommand};
+use pobsdlib::DataBase;
+use std::process;
+
+mod browser;
+mod commands;
+mod output;
+mod source;
+
+use crate::output::{OutputFormat, Terminal};
+use crate::source::{Source, DEFAULT_SOURCE};
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "pobsd",
+    version,
+    about = "Query and maintain the PlayOnBSD database"
+)]
+pub struct Cli {
+    /// Database to use: a path, an http(s) url or - for stdin
+    #[arg(long, global = true, env = "POBSD_DB", default_value = DEFAULT_SOURCE)]
+    pub db: String,
+    /// Output format
+    #[arg(
+        long,
+        short,
+        global = true,
+        visible_alias = "format",
+        value_enum,
+        default_value = "table"
+    )]
+    pub output: OutputFormat,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Rewrite the database in its canonical form
+    Format(commands::format::FormatArgs),
+    /// Check the database against the lint rules
+    Lint(commands::lint::LintArgs),
+    /// Search games
+    Query(commands::query::QueryArgs),
+    /// Show a game
+    Show(commands::show::ShowArgs),
+    /// Show statistics about the database
+    Stats,
+    /// Show the changes between two databases
+    Diff(commands::diff::DiffArgs),
+    /// Export the whole database
+    Export(commands::export::ExportArgs),
+    /// Browse the database in a full-screen interface
+    Browse,
+    /// Add a new game to the database
+    New(Box<commands::new::NewArgs>),
+    /// Print the JSON Schema of the JSON export
+    Schema,
+    /// Set the Updated date of the games modified since a base version
+    Bump(commands::bump::BumpArgs),
+}
+
+/// Options shared by all commands.
+pub struct Context {
+    pub source: Source,
+    pub output: OutputFormat,
+    pub terminal: Terminal,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let context = Context {
+        source: Source::parse(&cli.db),
+        output: cli.output,
+        terminal: Terminal::detect(),
+    };
+    let result = match cli.command {
+        Command::Format(args) => commands::format::run(args, &context),
+        Command::Lint(args) => commands::lint::run(args, &context),
+        Command::Query(args) => commands::query::run(args, &context),
+        Command::Show(args) => commands::show::run(args, &context),
+        Command::Stats => commands::stats::run(&context),
+        Command::Diff(args) => commands::diff::run(args, &context),
+        Command::Export(args) => commands::export::run(args, &context),
+        Command::Browse => commands::browse::run(&context),
+        Command::New(args) => commands::new::run(*args, &context),
+        Command::Schema => {
+            println!("{}", DataBase::json_schema());
+            Ok(0)
+        }
+        Command::Bump(args) => commands::bump::run(args, &context),
+    };
+    match result {
+        Ok(0) => {}
+        Ok(code) => process::exit(code),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use clap::ValueEnum;
+use pobsdlib::utils::csv_io::{COLUMNS, LIST_SEPARATOR};
+use pobsdlib::utils::{game_column, write_games, write_rows, CsvOptions};
+use pobsdlib::Game;
+use serde::Serialize;
+use std::io::{self, IsTerminal};
+
+/// Format of the output of the commands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// aligned columns
+    Table,
+    /// JSON document
+    Json,
+    /// comma separated values
+    Csv,
+    /// tab separated values
+    Tsv,
+    /// database format
+    Db,
+}
+
+/// Fields of a game that can be selected, named as in
+/// the JSON output.
+pub const GAME_FIELDS: [&str; 17] = COLUMNS;
+
+/// Fields displayed in tables when none are selected.
+pub const TABLE_FIELDS: [&str; 5] = ["id", "name", "engine", "runtime", "year"];
+
+/// Return the value of the given field of the game, lists
+/// being joined as in the CSV output.
+pub fn game_field(game: &Game, field: &str) -> Option<String> {
+    game_column(game, field, LIST_SEPARATOR)
+}
+
+/// Parse a comma separated list of fields.
+pub fn parse_fields(fields: &str) -> Result<Vec<String>, String> {
+    let mut parsed: Vec<String> = Vec::new();
+    for field in fields.split(',').map(|field| field.trim().to_lowercase()) {
+        if !GAME_FIELDS.contains(&field.as_str()) {
+            return Err(format!(
+                "Unknown field: {} (expected one of {})",
+                field,
+                GAME_FIELDS.join(", ")
+            ));
+        }
+        parsed.push(field);
+    }
+    Ok(parsed)
+}
+
+/// What the output is written to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Terminal {
+    /// width available for tables, if known.
+    pub width: Option<usize>,
+    /// whether escape sequences can be used.
+    pub colour: bool,
+}
+
+impl Terminal {
+    /// Output not written to a terminal: no colour and
+    /// no width limit, so that the output is stable.
+    pub fn plain() -> Self {
+        Terminal::default()
+    }
+    /// Detect whether stdout is a terminal.
+    pub fn detect() -> Self {
+        if !io::stdout().is_terminal() {
+            return Terminal::plain();
+        }
+        let width = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse::<usize>().ok())
+            .or_else(|| terminal_size::terminal_size().map(|(width, _)| width.0 as usize));
+        Terminal {
+            width,
+            colour: std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
+
+// Columns are not shrunk below this width.
+const MIN_WIDTH: usize = 8;
+
+/// Rows of text printed as aligned columns.
+#[derive(Clone, Debug, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+    // Width of each column, the widest ones being shrunk
+    // until the table fits in the given width.
+    fn widths(&self, width: Option<usize>) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+        if let Some(width) = width {
+            let separators = 2 * widths.len().saturating_sub(1);
+            while widths.iter().sum::<usize>() + separators > width {
+                match widths.iter_mut().max() {
+                    Some(widest) if *widest > MIN_WIDTH => *widest -= 1,
+                    _ => break,
+                }
+            }
+        }
+        widths
+    }
+    /// Render the table, the last column being left unpadded.
+    /// Headers are written in bold when colours are allowed.
+    pub fn render(&self, terminal: &Terminal) -> String {
+        let widths = self.widths(terminal.width);
+        let mut output = String::new();
+        let header_row = if self.headers.iter().all(|header| header.is_empty()) {
+            None
+        } else {
+            Some(&self.headers)
+        };
+        for (index, row) in header_row.into_iter().chain(self.rows.iter()).enumerate() {
+            let mut line = String::new();
+            let last = row.len().saturating_sub(1);
+            for (i, cell) in row.iter().enumerate() {
+                let cell = truncate(cell, widths[i]);
+                if i == last {
+                    line.push_str(&cell);
+                } else {
+                    line.push_str(&format!("{:width$}  ", cell, width = widths[i]));
+                }
+            }
+            // trailing empty cells leave padding behind
+            let line = line.trim_end_matches(' ');
+            if index == 0 && header_row.is_some() && terminal.colour {
+                output.push_str(&format!("\x1b[1m{}\x1b[0m\n", line));
+            } else {
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+fn truncate(cell: &str, width: usize) -> String {
+    if cell.chars().count() <= width {
+        cell.to_string()
+    } else {
+        let mut cell: String = cell.chars().take(width.saturating_sub(1)).collect();
+        cell.push('…');
+        cell
+    }
+}
+
+/// Serialize the value as pretty JSON.
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value).expect("Values are serializable") + "\n"
+}
+
+/// Write the rows as CSV (or TSV) preceded by the header.
+pub fn render_rows(header: &[&str], rows: &[Vec<String>], format: OutputFormat) -> String {
+    let options = if format == OutputFormat::Tsv {
+        CsvOptions::tsv()
+    } else {
+        CsvOptions::csv()
+    };
+    let mut output: Vec<u8> = Vec::new();
+    write_rows(header, rows, &options, &mut output).expect("Rows are written in memory");
+    String::from_utf8(output).expect("Rows are valid UTF-8")
+}
+
+/// Write the games as CSV (or TSV) with the given fields,
+/// stores, genres and tags being joined by the separator.
+pub fn render_csv(
+    games: &[Game],
+    options: &mut CsvOptions,
+    fields: Option<&[String]>,
+) -> Result<String, String> {
+    if let Some(fields) = fields {
+        let fields: Vec<&str> = fields.iter().map(|field| field.as_str()).collect();
+        options
+            .set_columns(&fields)
+            .map_err(|err| err.to_string())?;
+    }
+    let mut output: Vec<u8> = Vec::new();
+    write_games(games, options, &mut output).map_err(|err| err.to_string())?;
+    String::from_utf8(output).map_err(|err| err.to_string())
+}
+
+/// Render the games in the given format, in the given order.
+/// When fields are given, only those fields are written
+/// (except for the database format which needs all of them).
+pub fn render_games(
+    games: &[Game],
+    format: OutputFormat,
+    fields: Option<&[String]>,
+    terminal: &Terminal,
+) -> String {
+    match format {
+        OutputFormat::Table => {
+            let fields: Vec<&str> = match fields {
+                Some(fields) => fields.iter().map(|field| field.as_str()).collect(),
+                None => TABLE_FIELDS.to_vec(),
+            };
+            let headers: Vec<String> = fields.iter().map(|field| field.to_uppercase()).collect();
+            let headers: Vec<&str> = headers.iter().map(|header| header.as_str()).collect();
+            let mut table = Table::new(&headers);
+            for game in games {
+                table.push(
+                    fields
+                        .iter()
+                        .map(|field| game_field(game, field).unwrap_or_default())
+                        .collect(),
+                );
+            }
+            table.render(terminal)
+        }
+        OutputFormat::Json => match fields {
+            Some(fields) => {
+                let games: Vec<serde_json::Map<String, serde_json::Value>> = games
+                    .iter()
+                    .map(|game| {
+                        let mut value = match serde_json::to_value(game) {
+                            Ok(serde_json::Value::Object(map)) => map,
+                            _ => serde_json::Map::new(),
+                        };
+                        fields
+                            .iter()
+                            .filter_map(|field| {
+                                value.remove(field).map(|value| (field.clone(), value))
+                            })
+                            .collect()
+                    })
+                    .collect();
+                to_json(&games)
+            }
+            None => to_json(games),
+        },
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let mut options = if format == OutputFormat::Tsv {
+                CsvOptions::tsv()
+            } else {
+                CsvOptions::csv()
+            };
+            // fields are checked by parse_fields
+            render_csv(games, &mut options, fields).expect("Fields are valid")
+        }
+        OutputFormat::Db => {
+            let mut output = String::new();
+            for game in games {
+                output.push_str(&game.to_string());
+                output.push('\n');
+            }
+            output
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_output {
+    use super::*;
+    fn table() -> Table {
+        let mut table = Table::new(&["ID", "NAME", "YEAR"]);
+        table.push(vec![
+            "1".to_string(),
+            "Shuggy".to_string(),
+            "2012".to_string(),
+        ]);
+        table.push(vec![
+            "10".to_string(),
+            "Aaaaaaaa for the Awesome".to_string(),
+            String::new(),
+        ]);
+        table
+    }
+    #[test]
+    fn plain_table() {
+        assert_eq!(
+            table().render(&Terminal::plain()),
+            "ID  NAME                      YEAR\n1   Shuggy                    2012\n10  Aaaaaaaa for the Awesome\n"
+        );
+    }
+    #[test]
+    fn narrow_table() {
+        let terminal = Terminal {
+            width: Some(20),
+            colour: true,
+        };
+        assert_eq!(
+            table().render(&terminal),
+            "\x1b[1mID  NAME        YEAR\x1b[0m\n1   Shuggy      2012\n10  Aaaaaaaa …\n"
+        );
+    }
+    #[test]
+    fn tsv() {
+        let game = Game {
+            id: 1,
+            name: "Shuggy".to_string(),
+            stores: Some(vec!["a".to_string(), "b".to_string()]),
+            ..Default::default()
+        };
+        let fields = parse_fields("id,name,stores").unwrap();
+        assert_eq!(
+            render_games(
+                &[game],
+                OutputFormat::Tsv,
+                Some(&fields),
+                &Terminal::plain()
+            ),
+            "id\tname\tstores\n1\tShuggy\ta, b\n"
+        );
+    }
+    #[test]
+    fn csv_and_fields() {
+        let game = Game {
+            id: 1,
+            name: "Shuggy".to_string(),
+            tags: Some(vec!["indie".into(), "free".into()]),
+            ..Default::default()
+        };
+        let fields = parse_fields("name, Tags").unwrap();
+        assert_eq!(
+            render_games(
+                &[game],
+                OutputFormat::Csv,
+                Some(&fields),
+                &Terminal::plain()
+            ),
+            "name,tags\nShuggy,\"indie, free\"\n"
+        );
+        assert!(parse_fields("name,colour").is_err());
+    }
+    #[test]
+    fn rows() {
+        let rows = vec![vec![
+            "tags".to_string(),
+            "indie, free".to_string(),
+            "2".to_string(),
+        ]];
+        assert_eq!(
+            render_rows(&["collection", "name", "games"], &rows, OutputFormat::Csv),
+            "collection,name,games\ntags,\"indie, free\",2\n"
+        );
+        assert_eq!(
+            render_rows(&["collection", "name", "games"], &rows, OutputFormat::Tsv),
+            "collection\tname\tgames\ntags\tindie, free\t2\n"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use pobsdlib::utils::{read_game_tree, read_games, CsvOptions};
+use pobsdlib::{DataBase, DataBaseBuilder};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Database used when no source is given.
+pub const DEFAULT_SOURCE: &str =
+    "https://raw.githubusercontent.com/playonbsd/OpenBSD-Games-Database/main/openbsd-games.db";
+
+/// Where the database is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Path(String),
+    Url(String),
+    Stdin,
+}
+
+impl Source {
+    pub fn parse(source: &str) -> Self {
+        if source == "-" {
+            Source::Stdin
+        } else if source.starts_with("https://") || source.starts_with("http://") {
+            Source::Url(source.to_string())
+        } else {
+            Source::Path(source.to_string())
+        }
+    }
+    /// Return the content of the database.
+    pub fn read(&self) -> Result<String, String> {
+        match self {
+            Source::Path(path) => {
+                fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path, err))
+            }
+            Source::Url(url) => reqwest::blocking::get(url)
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.text())
+                .map_err(|err| format!("Could not fetch {}: {}", url, err)),
+            Source::Stdin => {
+                let mut data = String::new();
+                io::stdin()
+                    .read_to_string(&mut data)
+                    .map_err(|err| format!("Could not read stdin: {}", err))?;
+                Ok(data)
+            }
+        }
+    }
+    /// Return the database, the values being kept as they are
+    /// in the source (no cover expansion, no Updated filling).
+    /// Sources holding a JSON export are read as such, as
+    /// well as .csv and .tsv files written by the export and
+    /// directories holding a game tree.
+    pub fn load(&self) -> Result<DataBase, String> {
+        if let Source::Path(path) = self {
+            if Path::new(path).is_dir() {
+                return read_game_tree(Path::new(path))
+                    .map_err(|err| format!("Could not read {}: {}", path, err));
+            }
+        }
+        let data = self.read()?;
+        if let Some(options) = self.csv_options() {
+            read_games(data.as_bytes(), &options)
+                .map(DataBase::from_games)
+                .map_err(|err| format!("Invalid CSV database: {}", err))
+        } else if is_json(&data) {
+            DataBase::from_json(&data).map_err(|err| format!("Invalid JSON database: {}", err))
+        } else {
+            Ok(DataBaseBuilder::verbatim().build_from_string(data))
+        }
+    }
+    /// Return the content of the database, which must be in
+    /// the text format of the database: the commands editing
+    /// the database refuse the other sources accepted by load
+    /// (JSON, CSV and TSV exports, game trees).
+    pub fn read_text(&self) -> Result<String, String> {
+        let refused = |source: &str| format!("{} is not a database text file", source);
+        if let Source::Path(path) = self {
+            if Path::new(path).is_dir() || self.csv_options().is_some() {
+                return Err(refused(path));
+            }
+        }
+        let data = self.read()?;
+        if is_json(&data) {
+            return Err(refused(match self {
+                Source::Path(path) | Source::Url(path) => path,
+                Source::Stdin => "stdin",
+            }));
+        }
+        Ok(data)
+    }
+    // Options of the .csv and .tsv files.
+    fn csv_options(&self) -> Option<CsvOptions> {
+        match self {
+            Source::Path(path) if path.ends_with(".csv") => Some(CsvOptions::csv()),
+            Source::Path(path) if path.ends_with(".tsv") => Some(CsvOptions::tsv()),
+            _ => None,
+        }
+    }
+}
+
+// JSON exports start with an object, database text files
+// with a Game line.
+fn is_json(data: &str) -> bool {
+    data.trim_start().starts_with('{')
+}
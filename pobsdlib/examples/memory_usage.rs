@@ -0,0 +1,44 @@
+//! Print the memory allocated by a database, measured with a
+//! counting allocator:
+//! `cargo run --release --example memory_usage -- openbsd-games.db`
+extern crate pobsdlib;
+
+use pobsdlib::DataBaseBuilder;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counter;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "tests/data/test-games.db".to_string());
+    // the text is freed once parsed
+    let before = ALLOCATED.load(Ordering::SeqCst);
+    let data = fs::read_to_string(&path).expect("Could not read the database");
+    let database = DataBaseBuilder::verbatim().build_from_string(data);
+    let after = ALLOCATED.load(Ordering::SeqCst);
+    println!(
+        "{} games: {} bytes",
+        database.get_all_games().count,
+        after - before
+    );
+}
@@ -0,0 +1,67 @@
+extern crate pobsdlib;
+use pobsdlib::utils::diff_databases;
+use pobsdlib::{DataBase, DataBaseBuilder};
+use std::{env, fs, process};
+
+// Can be used by git either as an external diff tool
+// or as a textconv filter:
+//
+// .gitattributes
+//     openbsd-games.db diff=pobsd
+// .git/config
+//     [diff "pobsd"]
+//         command = diff_game_db
+// or
+//     [diff "pobsd"]
+//         textconv = diff_game_db --textconv
+const USAGE: &str = "Usage: diff_game_db OLD NEW
+       diff_game_db --textconv FILE
+       diff_game_db PATH OLD OLD-HEX OLD-MODE NEW NEW-HEX NEW-MODE";
+
+fn load(filename: &str) -> DataBase {
+    match fs::read_to_string(filename) {
+        Ok(content) => DataBaseBuilder::verbatim().build_from_string(content),
+        Err(err) => {
+            eprintln!("Could not read {}: {}", filename, err);
+            process::exit(2);
+        }
+    }
+}
+
+// Print the games sorted by name so that reordering
+// the database does not show up in the diff.
+fn textconv(filename: &str) {
+    let mut games = load(filename).get_all_games().items;
+    games.sort_by_key(|game| game.name.to_lowercase());
+    for game in games {
+        println!("{}", game);
+    }
+}
+
+fn diff(old: &str, new: &str) {
+    let changes = diff_databases(&load(old), &load(new));
+    for change in changes {
+        println!("{}", change);
+    }
+}
+
+// Deprecated in favour of `pobsd diff`, kept for one release
+// so that existing scripts keep working.
+fn main() {
+    eprintln!("diff_game_db is deprecated, use `pobsd diff` instead");
+    let args: Vec<String> = env::args().collect();
+    match args.len() {
+        3 if args[1] == "--textconv" => textconv(&args[2]),
+        3 => diff(&args[1], &args[2]),
+        // called by git as GIT_EXTERNAL_DIFF
+        8 => {
+            println!("diff {}", args[1]);
+            diff(&args[2], &args[5]);
+        }
+        _ => {
+            eprintln!("Wrong number of arguments");
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
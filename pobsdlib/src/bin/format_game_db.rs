@@ -1,28 +1,72 @@
 extern crate pobsdlib;
-use pobsdlib::utils::database_builder::DataBaseBuilder;
-use std::{env, path, process};
+use pobsdlib::utils::{format_database, format_diff};
+use std::{env, fs, process};
 
+const USAGE: &str = "Usage: format_game_db [--check | --in-place] FILE";
+
+// Without option, the formatted database is printed on stdout.
+// With --check, nothing is written: the differences with the
+// formatted database are printed and the exit code is 1 if the
+// file is not formatted.
+// With --in-place, the file is replaced by its formatted version.
+// Deprecated in favour of `pobsd format`, kept for one release
+// so that existing scripts keep working.
 fn main() {
+    eprintln!("format_game_db is deprecated, use `pobsd format` instead");
     let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Not enough arguments");
-        process::exit(1);
-    }
-    if args.len() > 2 {
-        eprintln!("Too many arguments");
-        process::exit(1);
+    let mut check = false;
+    let mut in_place = false;
+    let mut filename: Option<String> = None;
+    for arg in args.into_iter().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--in-place" => in_place = true,
+            _ if filename.is_none() => filename = Some(arg),
+            _ => {
+                eprintln!("Too many arguments");
+                eprintln!("{}", USAGE);
+                process::exit(2);
+            }
+        }
     }
-    let path = path::Path::new(&args[1]);
-    if path.is_file() {
-        let game_db = DataBaseBuilder::new(true, true).build_from_file(&args[1]);
-        let games = game_db.get_all_games();
-        for mut game in games.items {
-            if let Some(date) = game.added {
-                game.added = Some(date);
+    let filename = match filename {
+        Some(filename) if !(check && in_place) => filename,
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    let data = match fs::read_to_string(&filename) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("Could not read {}: {}", filename, err);
+            process::exit(2);
+        }
+    };
+    let formatted = match format_database(&data) {
+        Ok(formatted) => formatted,
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}:{}", filename, error);
+            }
+            eprintln!("{} cannot be formatted without losing data", filename);
+            process::exit(2);
+        }
+    };
+    if check {
+        if formatted != data {
+            print!("{}", format_diff(&data, &formatted));
+            eprintln!("{} is not formatted", filename);
+            process::exit(1);
+        }
+    } else if in_place {
+        if formatted != data {
+            if let Err(err) = fs::write(&filename, formatted) {
+                eprintln!("Could not write {}: {}", filename, err);
+                process::exit(2);
             }
-            println!("{}", game);
         }
     } else {
-        eprintln!("This is not a file");
+        print!("{}", formatted);
     }
 }
@@ -0,0 +1,46 @@
+extern crate pobsdlib;
+use pobsdlib::utils::merge_databases;
+use std::{env, fs, process};
+
+// To be used as a git merge driver:
+//
+// .gitattributes
+//     openbsd-games.db merge=pobsd
+// .git/config
+//     [merge "pobsd"]
+//         name = openbsd-games.db merge driver
+//         driver = merge_game_db %O %A %B
+//
+// The result is written in place of ours (%A).
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 4 {
+        eprintln!("Not enough arguments");
+        eprintln!("Usage: merge_game_db BASE OURS THEIRS");
+        process::exit(2);
+    }
+    if args.len() > 4 {
+        eprintln!("Too many arguments");
+        eprintln!("Usage: merge_game_db BASE OURS THEIRS");
+        process::exit(2);
+    }
+    let mut contents: Vec<String> = Vec::with_capacity(3);
+    for filename in &args[1..] {
+        match fs::read_to_string(filename) {
+            Ok(content) => contents.push(content),
+            Err(err) => {
+                eprintln!("Could not read {}: {}", filename, err);
+                process::exit(2);
+            }
+        }
+    }
+    let result = merge_databases(&contents[0], &contents[1], &contents[2]);
+    if let Err(err) = fs::write(&args[2], result.content) {
+        eprintln!("Could not write {}: {}", args[2], err);
+        process::exit(2);
+    }
+    if result.conflicts > 0 {
+        eprintln!("{} conflict(s) left in {}", result.conflicts, args[2]);
+        process::exit(1);
+    }
+}
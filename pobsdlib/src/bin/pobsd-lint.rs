@@ -0,0 +1,89 @@
+extern crate pobsdlib;
+extern crate serde_json;
+use pobsdlib::models::Severity;
+use pobsdlib::utils::lint_database;
+use pobsdlib::LintConfig;
+use std::{env, fs, process};
+
+const USAGE: &str = "Usage: pobsd-lint [--config FILE] [--format text|json|github] FILE";
+
+// Deprecated in favour of `pobsd lint`, kept for one release
+// so that existing scripts keep working.
+fn main() {
+    eprintln!("pobsd-lint is deprecated, use `pobsd lint` instead");
+    let args: Vec<String> = env::args().collect();
+    let mut config = LintConfig::new();
+    let mut format = "text".to_string();
+    let mut filename: Option<String> = None;
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => match args.next() {
+                Some(path) => match LintConfig::from_file(&path) {
+                    Ok(content) => config = content,
+                    Err(err) => {
+                        eprintln!("Could not read the configuration {}: {}", path, err);
+                        process::exit(2);
+                    }
+                },
+                None => {
+                    eprintln!("{}", USAGE);
+                    process::exit(2);
+                }
+            },
+            "--format" => match args.next() {
+                Some(value) if ["text", "json", "github"].contains(&value.as_str()) => {
+                    format = value
+                }
+                _ => {
+                    eprintln!("{}", USAGE);
+                    process::exit(2);
+                }
+            },
+            _ if filename.is_none() => filename = Some(arg),
+            _ => {
+                eprintln!("Too many arguments");
+                eprintln!("{}", USAGE);
+                process::exit(2);
+            }
+        }
+    }
+    let filename = match filename {
+        Some(filename) => filename,
+        None => {
+            eprintln!("Not enough arguments");
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    let data = match fs::read_to_string(&filename) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("Could not read {}: {}", filename, err);
+            process::exit(2);
+        }
+    };
+    let diagnostics = lint_database(&data, &config);
+    match format.as_str() {
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&diagnostics).expect("Diagnostics are serializable")
+        ),
+        "github" => {
+            for diagnostic in &diagnostics {
+                println!("{}", diagnostic.to_github_annotation(&filename));
+            }
+        }
+        _ => {
+            for diagnostic in &diagnostics {
+                println!("{}:{}", filename, diagnostic);
+            }
+        }
+    }
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        process::exit(1);
+    }
+}
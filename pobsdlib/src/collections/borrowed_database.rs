@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::collections::{DataBase, QueryResult};
+use crate::models::{Field, FieldKind, GameRef};
+use crate::utils::database_builder::Cursor;
+use crate::utils::game_dispatch::game_ref_dispatch;
+
+/// Store the games of a database without copying its text:
+/// the values of the games are slices of the text, which must
+/// outlive the database. As in DataBase, the item collections
+/// are stored by FieldKind (the facets of FieldKind::DEFAULT)
+/// and map each value to the uuids of its games, their keys
+/// being interned: the engine, runtime, genres, tags, year,
+/// developer and publisher of a game are the very slices used
+/// as keys (the first occurrence of the value in the text).
+///
+/// Values are kept as written in the database, as with
+/// DataBaseBuilder::verbatim, and the lookups perform an exact
+/// matching: unlike DataBase, the borrowed database has no
+/// normalizer (see DataBase::set_normalizer). to_database
+/// returns the owned database when needed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BorrowedDataBase<'a> {
+    pub(crate) games: HashMap<u64, GameRef<'a>>,
+    pub(crate) facets: BTreeMap<FieldKind, HashMap<&'a str, BTreeSet<u64>>>,
+}
+
+impl<'a> Default for BorrowedDataBase<'a> {
+    fn default() -> Self {
+        BorrowedDataBase {
+            games: HashMap::new(),
+            facets: FieldKind::DEFAULT
+                .iter()
+                .map(|kind| (*kind, HashMap::new()))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> BorrowedDataBase<'a> {
+    /// Parse the text of the database.
+    pub fn parse(data: &'a str) -> Self {
+        let mut database = BorrowedDataBase::default();
+        let mut cursor = Cursor::new();
+        for line in data.lines() {
+            // empty lines carry no information
+            if line.trim().is_empty() {
+                continue;
+            }
+            game_ref_dispatch(Field::from(line), &mut database, &mut cursor);
+        }
+        // in the order of the text, so that the interned values
+        // are their first occurrences
+        let mut games: Vec<(usize, u64)> = database
+            .games
+            .values()
+            .map(|game| (game.id, game.uuid))
+            .collect();
+        games.sort();
+        for (_, uuid) in games {
+            database.index_game(uuid);
+        }
+        database
+    }
+    // Reference the game in the item collections, its values
+    // being replaced by the interned ones.
+    fn index_game(&mut self, uuid: u64) {
+        let game = match self.games.get_mut(&uuid) {
+            Some(game) => game,
+            None => return,
+        };
+        for (kind, collection) in self.facets.iter_mut() {
+            for value in kind.slices_mut(game) {
+                let key = collection
+                    .get_key_value(*value)
+                    .map_or(*value, |(key, _)| *key);
+                collection.entry(key).or_default().insert(uuid);
+                *value = key;
+            }
+        }
+    }
+    /// Return the owned database, identical to the one built
+    /// from the same text by DataBaseBuilder::verbatim.
+    pub fn to_database(&self) -> DataBase {
+        let mut games: Vec<&GameRef> = self.games.values().collect();
+        games.sort_by_key(|game| game.id);
+        DataBase::from_games(games.into_iter().map(GameRef::to_game).collect())
+    }
+    /// Return all games of the database.
+    pub fn get_all_games(&self) -> QueryResult<&GameRef<'a>> {
+        QueryResult::new(self.games.values().collect())
+    }
+    /// Return the game with the given uuid.
+    pub fn get_game_by_id(&self, id: u64) -> Option<&GameRef<'a>> {
+        self.games.get(&id)
+    }
+    /// Return the games of the database having the given value
+    /// for the given facet. It performs an exact matching.
+    pub fn games_by(&self, kind: FieldKind, name: &str) -> QueryResult<&GameRef<'a>> {
+        QueryResult::new(
+            self.facets
+                .get(&kind)
+                .and_then(|items| items.get(name))
+                .into_iter()
+                .flatten()
+                .filter_map(|uuid| self.games.get(uuid))
+                .collect(),
+        )
+    }
+    /// Return all values of the given facet.
+    pub fn items(&self, kind: FieldKind) -> QueryResult<&'a str> {
+        QueryResult::new(
+            self.facets
+                .get(&kind)
+                .into_iter()
+                .flat_map(HashMap::keys)
+                .copied()
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_borrowed_database {
+    use super::*;
+    use crate::DataBaseBuilder;
+    const DATA: &str = "Game\tShuggy\nEngine\tFNA\nTags\tindie, free\nTags\tpuzzle\nAdded\t2020-01-01\nGame\tKathy Rain\nCover\nEngine\tFNA\nTags\tindie\n";
+    #[test]
+    fn interning() {
+        let database = BorrowedDataBase::parse(DATA);
+        let games = database.games_by(FieldKind::Engine, "FNA").items;
+        assert_eq!(games.len(), 2);
+        let (first, second) = (games[0].engine.unwrap(), games[1].engine.unwrap());
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(games[0].tags, Some(vec!["indie", "free", "puzzle"]));
+        assert!(std::ptr::eq(
+            games[0].tags.as_ref().unwrap()[0],
+            games[1].tags.as_ref().unwrap()[0]
+        ));
+        assert_eq!(database.games_by(FieldKind::Tag, "indie").count, 2);
+        assert_eq!(database.games_by(FieldKind::Tag, "none").count, 0);
+        assert_eq!(database.games_by(FieldKind::Store, "Steam").count, 0);
+        let mut tags = database.items(FieldKind::Tag).items;
+        tags.sort();
+        assert_eq!(tags, vec!["free", "indie", "puzzle"]);
+    }
+    #[test]
+    fn owned_database() {
+        assert_eq!(
+            BorrowedDataBase::parse(DATA).to_database(),
+            DataBaseBuilder::verbatim().build_from_string(DATA.to_string())
+        );
+    }
+}
@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use schemars::{schema_for, JsonSchema};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use crate::collections::QueryResult;
-use crate::models::{Game, GameFilter, Item};
+use crate::models::{FieldKind, Game, GameFilter, Item, Normalizer, Symbol};
 
 /// Store the game database in different collections.
 /// With the exception of the get_game_by_id query,
@@ -16,7 +18,8 @@ use crate::models::{Game, GameFilter, Item};
 /// HashMap.
 ///
 /// ## The item collections
-/// Each item collection is stored using a HashMap.
+/// Each item collection (or facet) is stored using a
+/// HashMap, the facets being stored by FieldKind.
 /// The name of each item is used as a key while
 /// the value is the corresponding Item struct.
 ///
@@ -30,8 +33,11 @@ use crate::models::{Game, GameFilter, Item};
 /// - devs
 /// - publishers
 ///
+/// The store kinds, statuses and setup tools can be
+/// added with register_facet.
 /// Those collections are used to retrieve all games
-/// associated with a specific item.
+/// associated with a specific item (see games_by and
+/// items).
 ///
 /// ## Limitations
 /// In its current state, queries cannot be chained
@@ -39,19 +45,302 @@ use crate::models::{Game, GameFilter, Item};
 /// game_contains_and and game_contains_or most of the
 /// useful queries can be performed.
 ///
-#[derive(Serialize, Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
 pub struct DataBase {
     pub(crate) games: HashMap<u64, Game>,
-    pub(crate) engines: HashMap<String, Item>,
-    pub(crate) runtimes: HashMap<String, Item>,
-    pub(crate) genres: HashMap<String, Item>,
-    pub(crate) tags: HashMap<String, Item>,
-    pub(crate) years: HashMap<String, Item>,
-    pub(crate) devs: HashMap<String, Item>,
-    pub(crate) publis: HashMap<String, Item>,
+    pub(crate) facets: BTreeMap<FieldKind, HashMap<Symbol, Item>>,
+    #[serde(skip)]
+    pub(crate) normalizer: Option<Normalizer>,
+}
+
+impl Default for DataBase {
+    fn default() -> Self {
+        DataBase {
+            games: HashMap::new(),
+            facets: FieldKind::DEFAULT
+                .iter()
+                .map(|kind| (*kind, HashMap::new()))
+                .collect(),
+            normalizer: None,
+        }
+    }
+}
+
+/// Reference the game in the item of the collection with the
+/// given key, creating the item (named after the value) if
+/// needed, and return the interned value: the symbol of the
+/// item, shared by the games, when the value is spelled as the
+/// item.
+pub(crate) fn index_item(
+    collection: &mut HashMap<Symbol, Item>,
+    key: &str,
+    value: &str,
+    uuid: u64,
+) -> Symbol {
+    if let Some(item) = collection.get_mut(key) {
+        item.games.insert(uuid);
+        if item.name == value {
+            return item.name.clone();
+        }
+        return Symbol::from(value);
+    }
+    let name = Symbol::from(value);
+    let key = if key == value {
+        name.clone()
+    } else {
+        Symbol::from(key)
+    };
+    collection.insert(
+        key,
+        Item {
+            name: name.clone(),
+            games: BTreeSet::from([uuid]),
+        },
+    );
+    name
+}
+
+// Remove the game from the item of the collection with the
+// given key, the item being removed when it has no game left.
+fn unindex_item(collection: &mut HashMap<Symbol, Item>, key: &str, uuid: u64) {
+    if let Some(item) = collection.get_mut(key) {
+        item.games.remove(&uuid);
+        if item.games.is_empty() {
+            collection.remove(key);
+        }
+    }
+}
+
+// Return the key of the value in the item collection of the
+// facet: the value itself, or its canonical key when the
+// database is normalized.
+fn facet_key<'a>(normalizer: &Option<Normalizer>, kind: FieldKind, value: &'a str) -> Cow<'a, str> {
+    match normalizer {
+        Some(normalizer) => Cow::Owned(normalizer.key(kind, value)),
+        None => Cow::Borrowed(value),
+    }
 }
 
 impl DataBase {
+    /// Build a database from a list of games, filling the
+    /// item collections along the way. The games are expected
+    /// to have their id already set.
+    pub fn from_games(games: Vec<Game>) -> Self {
+        let mut database = DataBase::default();
+        for game in games {
+            database.index_game(game);
+        }
+        database
+    }
+    /// Build a database from its JSON representation (as
+    /// produced by to_json). Only the games and the kinds of
+    /// the facets are read, the item collections being rebuilt
+    /// from the games (the facets added with register_facet
+    /// being registered again).
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let database: DataBase = serde_json::from_str(json)?;
+        let kinds = database.facets();
+        // the games are indexed in the database order, so that
+        // the items are named after the first spelling met in
+        // it (as in set_normalizer and register_facet)
+        let mut games: Vec<Game> = database.games.into_values().collect();
+        games.sort_by_key(|game| game.id);
+        let mut rebuilt = DataBase::from_games(games);
+        for kind in kinds {
+            rebuilt.register_facet(kind);
+        }
+        Ok(rebuilt)
+    }
+    /// Return the JSON representation of the database.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("The database is serializable")
+    }
+    /// Return the JSON Schema describing the JSON
+    /// representation of the database.
+    pub fn json_schema() -> String {
+        serde_json::to_string_pretty(&schema_for!(DataBase)).expect("The schema is serializable")
+    }
+    /// Insert the game in the game collection and reference
+    /// it in the item collections.
+    /// The values of the game are replaced by the symbols of
+    /// the items, so that they are shared.
+    pub(crate) fn index_game(&mut self, mut game: Game) {
+        let uuid = game.uuid;
+        let normalizer = &self.normalizer;
+        for (kind, collection) in self.facets.iter_mut() {
+            if kind.is_derived() {
+                for value in kind.values(&game) {
+                    let key = facet_key(normalizer, *kind, value);
+                    index_item(collection, &key, value, uuid);
+                }
+            } else {
+                for value in kind.symbols_mut(&mut game) {
+                    let key = facet_key(normalizer, *kind, value);
+                    *value = index_item(collection, &key, value, uuid);
+                }
+            }
+        }
+        self.games.insert(uuid, game);
+    }
+    /// Remove the game from the game collection and from the
+    /// item collections.
+    fn unindex_game(&mut self, uuid: u64) -> Option<Game> {
+        let game = self.games.remove(&uuid)?;
+        for (kind, collection) in self.facets.iter_mut() {
+            for value in kind.values(&game) {
+                unindex_item(collection, &facet_key(&self.normalizer, *kind, value), uuid);
+            }
+        }
+        Some(game)
+    }
+    /// Insert the game in the database, its uuid being computed
+    /// from its name and its id being kept. A game with the same
+    /// name is replaced and returned.
+    pub fn insert_game(&mut self, mut game: Game) -> Option<Game> {
+        game.uuid = Game::compute_uuid(&game.name);
+        let previous = self.unindex_game(game.uuid);
+        self.index_game(game);
+        previous
+    }
+    /// Replace the game with the same name by the given game,
+    /// which takes its id, and return the previous version.
+    /// Nothing is done when no game has this name.
+    pub fn update_game(&mut self, mut game: Game) -> Option<Game> {
+        let uuid = Game::compute_uuid(&game.name);
+        let previous = self.unindex_game(uuid)?;
+        game.uuid = uuid;
+        game.id = previous.id;
+        self.index_game(game);
+        Some(previous)
+    }
+    /// Remove the game with the given uuid and return it.
+    pub fn remove_game(&mut self, uuid: u64) -> Option<Game> {
+        self.unindex_game(uuid)
+    }
+    /// Rename the game with the given uuid and return its new
+    /// uuid. Nothing is done (and None returned) when there is
+    /// no such game or when another game has the new name.
+    pub fn rename_game(&mut self, uuid: u64, name: &str) -> Option<u64> {
+        let renamed = Game::compute_uuid(name);
+        if renamed != uuid && self.games.contains_key(&renamed) {
+            return None;
+        }
+        let mut game = self.unindex_game(uuid)?;
+        game.name = name.to_string();
+        game.uuid = renamed;
+        self.index_game(game);
+        Some(renamed)
+    }
+    /// Share the symbols of the database again, as in a database
+    /// built by index_game: a deserialized database holds its
+    /// own copy of each value, in each game and each item.
+    pub(crate) fn share_symbols(&mut self) {
+        for collection in self.facets.values_mut() {
+            *collection = collection
+                .drain()
+                .map(|(key, item)| {
+                    if key == item.name {
+                        (item.name.clone(), item)
+                    } else {
+                        (key, item)
+                    }
+                })
+                .collect();
+        }
+        for game in self.games.values_mut() {
+            for (kind, collection) in &self.facets {
+                for value in kind.symbols_mut(game) {
+                    let key = facet_key(&self.normalizer, *kind, value);
+                    match collection.get(&*key) {
+                        Some(item) if item.name == *value => *value = item.name.clone(),
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+    /// Index the given facet, if it is not already, so that
+    /// its items can be queried with games_by and items.
+    pub fn register_facet(&mut self, kind: FieldKind) {
+        if self.facets.contains_key(&kind) {
+            return;
+        }
+        let mut games: Vec<&Game> = self.games.values().collect();
+        games.sort_by_key(|game| game.id);
+        let mut collection = HashMap::new();
+        for game in games {
+            for value in kind.values(game) {
+                let key = facet_key(&self.normalizer, kind, value);
+                index_item(&mut collection, &key, value, game.uuid);
+            }
+        }
+        self.facets.insert(kind, collection);
+    }
+    /// Normalize the item collections with the given normalizer:
+    /// the values sharing a canonical key are gathered in a
+    /// single item, named after the first spelling met in the
+    /// database, and the lookups (games_by, get_game_by_tag...)
+    /// go through the normalizer. The games keep their own
+    /// spelling of the values.
+    pub fn set_normalizer(&mut self, normalizer: Normalizer) {
+        self.normalizer = Some(normalizer);
+        let mut games: Vec<Game> = self.games.drain().map(|(_, game)| game).collect();
+        games.sort_by_key(|game| game.id);
+        for collection in self.facets.values_mut() {
+            collection.clear();
+        }
+        for game in games {
+            self.index_game(game);
+        }
+    }
+    /// Return the normalizer of the database, if any.
+    pub fn normalizer(&self) -> Option<&Normalizer> {
+        self.normalizer.as_ref()
+    }
+    /// Return the facets indexed by the database.
+    pub fn facets(&self) -> Vec<FieldKind> {
+        self.facets.keys().copied().collect()
+    }
+    // Reference the game in the item of the facet matching the
+    // value (the facet being registered if needed) and return
+    // the interned value.
+    pub(crate) fn index_value(&mut self, kind: FieldKind, value: &str, uuid: u64) -> Symbol {
+        let key = facet_key(&self.normalizer, kind, value);
+        index_item(self.facets.entry(kind).or_default(), &key, value, uuid)
+    }
+    // Remove the game from the item of the facet matching the
+    // value, e.g. when the value of the game is replaced.
+    pub(crate) fn unindex_value(&mut self, kind: FieldKind, value: &str, uuid: u64) {
+        let key = facet_key(&self.normalizer, kind, value);
+        if let Some(collection) = self.facets.get_mut(&kind) {
+            unindex_item(collection, &key, uuid);
+        }
+    }
+    /// Return the games of the database having the given value
+    /// for the given facet (an empty result when the facet is
+    /// not registered). It performs an exact matching, unless
+    /// the database is normalized (see set_normalizer).
+    pub fn games_by(&self, kind: FieldKind, name: &str) -> QueryResult<Game> {
+        let mut games: Vec<Game> = Vec::new();
+        let key = facet_key(&self.normalizer, kind, name);
+        if let Some(item) = self.facets.get(&kind).and_then(|items| items.get(&*key)) {
+            for game in item.games.iter().filter_map(|id| self.games.get(id)) {
+                games.push(game.clone());
+            }
+        }
+        QueryResult::new(games)
+    }
+    /// Return all items of the given facet.
+    pub fn items(&self, kind: FieldKind) -> QueryResult<&Item> {
+        QueryResult::new(
+            self.facets
+                .get(&kind)
+                .into_iter()
+                .flat_map(HashMap::values)
+                .collect(),
+        )
+    }
     /// Return all games of the database.
     pub fn get_all_games(&self) -> QueryResult<Game> {
         let games: Vec<&Game> = self.games.values().collect();
@@ -81,79 +370,37 @@ impl DataBase {
     /// Return the games of the database using the given engine.
     /// It performs an exact matching.
     pub fn get_game_by_engine(&self, name: &str) -> QueryResult<Game> {
-        let mut games: Vec<Game> = Vec::new();
-        if let Some(engine) = self.engines.get(name) {
-            for id in &engine.games {
-                games.push(self.games.get(id).unwrap().clone());
-            }
-        }
-        QueryResult::new(games)
+        self.games_by(FieldKind::Engine, name)
     }
     /// Return the games of the database using the givent runtime.
     /// It performs an exact matching.
     pub fn get_game_by_runtime(&self, name: &str) -> QueryResult<Game> {
-        let mut games: Vec<Game> = Vec::new();
-        if let Some(runtime) = self.runtimes.get(name) {
-            for id in &runtime.games {
-                games.push(self.games.get(id).unwrap().clone());
-            }
-        }
-        QueryResult::new(games)
+        self.games_by(FieldKind::Runtime, name)
     }
     /// Return the games of the database classified in the given genre.
     /// It performs an exact matching.
     pub fn get_game_by_genre(&self, name: &str) -> QueryResult<Game> {
-        let mut games: Vec<Game> = Vec::new();
-        if let Some(genre) = self.genres.get(name) {
-            for id in &genre.games {
-                games.push(self.games.get(id).unwrap().clone());
-            }
-        }
-        QueryResult::new(games)
+        self.games_by(FieldKind::Genre, name)
     }
     /// Return the games of the database classified in the given tag.
     /// It performs an exact matching.
     pub fn get_game_by_tag(&self, name: &str) -> QueryResult<Game> {
-        let mut games: Vec<Game> = Vec::new();
-        if let Some(tag) = self.tags.get(name) {
-            for id in &tag.games {
-                games.push(self.games.get(id).unwrap().clone());
-            }
-        }
-        QueryResult::new(games)
+        self.games_by(FieldKind::Tag, name)
     }
     /// Return the games of the database released in the given year.
     /// It performs an exact matching.
     pub fn get_game_by_year(&self, year: &str) -> QueryResult<Game> {
-        let mut games: Vec<Game> = Vec::new();
-        if let Some(year) = self.years.get(&year.to_string()) {
-            for id in &year.games {
-                games.push(self.games.get(id).unwrap().clone());
-            }
-        }
-        QueryResult::new(games)
+        self.games_by(FieldKind::Year, year)
     }
     /// Return the games of the database developped by the given developper.
     /// It performs an exact matching.
     pub fn get_game_by_dev(&self, name: &str) -> QueryResult<Game> {
-        let mut games: Vec<Game> = Vec::new();
-        if let Some(dev) = self.devs.get(name) {
-            for id in &dev.games {
-                games.push(self.games.get(id).unwrap().clone());
-            }
-        }
-        QueryResult::new(games)
+        self.games_by(FieldKind::Dev, name)
     }
     /// Return the games of the database published by the given publisher.
     /// It performs an exact matching.
     pub fn get_game_by_publi(&self, name: &str) -> QueryResult<Game> {
-        let mut games: Vec<Game> = Vec::new();
-        if let Some(publi) = self.publis.get(name) {
-            for id in &publi.games {
-                games.push(self.games.get(id).unwrap().clone());
-            }
-        }
-        QueryResult::new(games)
+        self.games_by(FieldKind::Publi, name)
     }
     /// Return the games that **contains**
     /// the given string for each field.
@@ -203,37 +450,30 @@ impl DataBase {
     }
     /// Return all engines of the database.
     pub fn get_all_engines(&self) -> QueryResult<&Item> {
-        let engines = self.engines.values().collect();
-        QueryResult::new(engines)
+        self.items(FieldKind::Engine)
     }
     /// Return all runtimes of the database.
     pub fn get_all_runtimes(&self) -> QueryResult<&Item> {
-        let engines = self.runtimes.values().collect();
-        QueryResult::new(engines)
+        self.items(FieldKind::Runtime)
     }
     /// Return all genres of the database.
     pub fn get_all_genres(&self) -> QueryResult<&Item> {
-        let genres = self.genres.values().collect();
-        QueryResult::new(genres)
+        self.items(FieldKind::Genre)
     }
     /// Return all tags of the database.
     pub fn get_all_tags(&self) -> QueryResult<&Item> {
-        let tags = self.tags.values().collect();
-        QueryResult::new(tags)
+        self.items(FieldKind::Tag)
     }
     /// Return all years of the database.
     pub fn get_all_years(&self) -> QueryResult<&Item> {
-        let years = self.years.values().collect();
-        QueryResult::new(years)
+        self.items(FieldKind::Year)
     }
     /// Return all developpers of the database.
     pub fn get_all_devs(&self) -> QueryResult<&Item> {
-        let devs = self.devs.values().collect();
-        QueryResult::new(devs)
+        self.items(FieldKind::Dev)
     }
     /// Return all publishers of the database.
     pub fn get_all_publis(&self) -> QueryResult<&Item> {
-        let publis = self.publis.values().collect();
-        QueryResult::new(publis)
+        self.items(FieldKind::Publi)
     }
 }
@@ -0,0 +1,150 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::collections::DataBase;
+
+/// Version of the cache format, to be increased whenever
+/// the format or the DataBase (and Game) structs change.
+pub const CACHE_VERSION: u32 = 2;
+
+// The cache starts with a header made of the magic bytes,
+// the version, the hash of the source, the checksum of the
+// payload and its length (integers in little endian). The
+// payload is the bincode encoding of the database, indexes
+// included, so that no parsing nor indexing is needed.
+const MAGIC: &[u8; 8] = b"POBSDDB\0";
+const HEADER_LENGTH: usize = 8 + 4 + 8 + 8 + 8;
+
+/// Return the hash identifying a source of the cache (e.g.
+/// the text of the database). Unlike DefaultHasher, the hash
+/// (64-bit FNV-1a) is stable across Rust versions.
+pub fn source_hash(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in data {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    let mut buffer = [0u8; 4];
+    buffer.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(buffer)
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buffer = [0u8; 8];
+    buffer.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(buffer)
+}
+
+impl DataBase {
+    /// Return the cache of the database built from the
+    /// source with the given hash (see source_hash).
+    pub fn to_cache_bytes(&self, source: u64) -> Vec<u8> {
+        let payload = bincode::serialize(self).expect("The database is serializable");
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&CACHE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&source.to_le_bytes());
+        bytes.extend_from_slice(&source_hash(&payload).to_le_bytes());
+        bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        bytes
+    }
+    /// Read a cache written by to_cache_bytes. The cache is
+    /// rejected (InvalidData error) if it was written by
+    /// another version, for another source or if it is
+    /// corrupted.
+    pub fn from_cache_bytes(bytes: &[u8], source: u64) -> io::Result<Self> {
+        if bytes.len() < HEADER_LENGTH || &bytes[..8] != MAGIC {
+            return Err(invalid("not a database cache"));
+        }
+        if read_u32(bytes, 8) != CACHE_VERSION {
+            return Err(invalid("cache written by another version"));
+        }
+        if read_u64(bytes, 12) != source {
+            return Err(invalid("cache of another source"));
+        }
+        let payload = &bytes[HEADER_LENGTH..];
+        if read_u64(bytes, 28) != payload.len() as u64
+            || read_u64(bytes, 20) != source_hash(payload)
+        {
+            return Err(invalid("corrupted cache"));
+        }
+        let mut database: DataBase =
+            bincode::deserialize(payload).map_err(|err| invalid(&err.to_string()))?;
+        database.share_symbols();
+        Ok(database)
+    }
+    /// Write the cache of the database in the given file. The
+    /// file is replaced atomically so that a concurrent reader
+    /// never sees a partial cache.
+    pub fn save_cache<P: AsRef<Path>>(&self, path: P, source: u64) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        fs::write(&temporary, self.to_cache_bytes(source))?;
+        fs::rename(&temporary, path)
+    }
+    /// Load the database from the cache written by save_cache,
+    /// with a single read of the file.
+    pub fn load_cache<P: AsRef<Path>>(path: P, source: u64) -> io::Result<Self> {
+        DataBase::from_cache_bytes(&fs::read(path)?, source)
+    }
+}
+
+#[cfg(test)]
+mod test_database_cache {
+    use super::*;
+    use crate::models::{FieldKind, Game};
+    fn database() -> DataBase {
+        DataBase::from_games(vec![Game {
+            id: 1,
+            uuid: Game::compute_uuid("Shuggy"),
+            name: "Shuggy".to_string(),
+            engine: Some("FNA".into()),
+            added: chrono::NaiveDate::from_ymd_opt(2020, 1, 1),
+            ..Default::default()
+        }])
+    }
+    #[test]
+    fn round_trip() {
+        let bytes = database().to_cache_bytes(42);
+        assert_eq!(DataBase::from_cache_bytes(&bytes, 42).unwrap(), database());
+    }
+    #[test]
+    fn shared_symbols() {
+        let database = DataBase::from_cache_bytes(&database().to_cache_bytes(42), 42).unwrap();
+        let game = database.get_all_games().items.remove(0);
+        let engine = database.get_all_engines().items[0].name.clone();
+        assert!(game.engine.unwrap().ptr_eq(&engine));
+        let (key, _) = database.facets[&FieldKind::Engine]
+            .get_key_value("FNA")
+            .unwrap();
+        assert!(key.ptr_eq(&engine));
+    }
+    #[test]
+    fn invalidation() {
+        let mut bytes = database().to_cache_bytes(42);
+        assert!(DataBase::from_cache_bytes(&bytes, 43).is_err());
+        assert!(DataBase::from_cache_bytes(&bytes[..20], 42).is_err());
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert!(DataBase::from_cache_bytes(&bytes, 42).is_err());
+        bytes[last] ^= 1;
+        bytes[8] = 0;
+        assert!(DataBase::from_cache_bytes(&bytes, 42).is_err());
+    }
+    #[test]
+    fn stable_hash() {
+        assert_eq!(source_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(source_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -0,0 +1,92 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::collections::{DataBase, SnapshotStore};
+use crate::models::{Commit, GameChange};
+
+/// A HistoryEvent associates a change of a game
+/// with the commit introducing it.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEvent {
+    /// commit introducing the change.
+    pub commit: Commit,
+    /// the change itself.
+    pub change: GameChange,
+}
+
+/// Store the history of the database as recorded in
+/// a git repository.
+///
+/// ## The event collection
+/// Every change of every game is stored as a HistoryEvent,
+/// from the oldest to the newest.
+///
+/// ## The timeline collection
+/// The timelines are stored using a HashMap. The uuid of
+/// each game is used as key while the value is the list
+/// of the positions of the events concerning said game
+/// in the event collection.
+///
+/// ## The snapshot store
+/// The state of the database at each commit is kept in a
+/// SnapshotStore, allowing to query the database as it was
+/// at a given date.
+#[derive(Serialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct History {
+    pub(crate) commits: Vec<Commit>,
+    pub(crate) events: Vec<HistoryEvent>,
+    pub(crate) timelines: HashMap<u64, Vec<usize>>,
+    #[serde(skip)]
+    pub(crate) snapshots: SnapshotStore,
+}
+
+impl History {
+    /// Add the changes introduced by a commit to the history
+    /// as well as the resulting state of the database.
+    pub(crate) fn push(&mut self, commit: Commit, changes: Vec<GameChange>, database: &DataBase) {
+        self.snapshots.push(commit.clone(), database);
+        for change in changes {
+            self.timelines
+                .entry(change.uuid)
+                .or_default()
+                .push(self.events.len());
+            self.events.push(HistoryEvent {
+                commit: commit.clone(),
+                change,
+            });
+        }
+        self.commits.push(commit);
+    }
+    /// Return the commits modifying the database, from
+    /// the oldest to the newest.
+    pub fn get_all_commits(&self) -> &[Commit] {
+        &self.commits
+    }
+    /// Return the events concerning the game with the
+    /// given uuid, from the oldest to the newest.
+    pub fn get_game_history(&self, uuid: u64) -> Vec<HistoryEvent> {
+        match self.timelines.get(&uuid) {
+            Some(positions) => positions
+                .iter()
+                .map(|&position| self.events[position].clone())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+    /// Return the last events of the history, from the
+    /// newest to the oldest.
+    pub fn get_latest_events(&self, count: usize) -> Vec<HistoryEvent> {
+        self.events.iter().rev().take(count).cloned().collect()
+    }
+    /// Return the snapshots of the database.
+    pub fn get_snapshots(&self) -> &SnapshotStore {
+        &self.snapshots
+    }
+    /// Return the database as it was at the end of the
+    /// given day, or None if the database did not exist yet.
+    pub fn get_database_as_of(&self, date: NaiveDate) -> Option<DataBase> {
+        self.snapshots
+            .get_snapshot_as_of(date)
+            .map(|snapshot| snapshot.to_database())
+    }
+}
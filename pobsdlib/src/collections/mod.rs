@@ -1,5 +1,15 @@
+pub use crate::collections::borrowed_database::BorrowedDataBase;
 pub use crate::collections::database::DataBase;
+pub use crate::collections::database_cache::{source_hash, CACHE_VERSION};
+pub use crate::collections::history::{History, HistoryEvent};
 pub use crate::collections::query_result::QueryResult;
+pub use crate::collections::snapshot_store::{Snapshot, SnapshotStore};
 
+pub mod borrowed_database;
 pub mod database;
+pub mod database_cache;
+pub mod history;
 pub mod query_result;
+pub mod snapshot_store;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
@@ -1,4 +1,6 @@
-#[derive(Serialize, Clone, Default, Debug)]
+use schemars::JsonSchema;
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default, Debug)]
 /// The QueryResult struct, as its name implies,
 /// represents the result of a database query.
 /// It has two fields: a count for the number
@@ -0,0 +1,180 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::collections::DataBase;
+use crate::models::{Commit, Game};
+
+// On average, a chunk holds CHUNK_FACTOR games.
+const CHUNK_FACTOR: u64 = 32;
+
+type Chunk = Arc<Vec<Arc<Game>>>;
+
+/// A Snapshot is the state of the database at a given
+/// commit.
+/// The games are stored in the database order, split
+/// in chunks which are shared with the other snapshots
+/// when they did not change.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// commit of the snapshot.
+    pub commit: Commit,
+    chunks: Vec<Chunk>,
+}
+
+impl Snapshot {
+    /// Return the number of games in the snapshot.
+    pub fn count(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+    /// Rebuild the database as it was at the commit of the
+    /// snapshot. The ids are given back according to the
+    /// position of the games in the snapshot.
+    pub fn to_database(&self) -> DataBase {
+        let mut games: Vec<Game> = Vec::with_capacity(self.count());
+        for (position, game) in self
+            .chunks
+            .iter()
+            .flat_map(|chunk| chunk.iter())
+            .enumerate()
+        {
+            let mut game = game.as_ref().clone();
+            game.id = position + 1;
+            games.push(game);
+        }
+        DataBase::from_games(games)
+    }
+}
+
+/// Store the successive states of the database.
+///
+/// To keep the store compact, a snapshot does not hold
+/// a full copy of the database: the games are split in
+/// chunks whose boundaries depend on the uuid of the games
+/// (and not on their position), so that adding or removing a
+/// game only changes the chunk it belongs to. Unchanged games
+/// and unchanged chunks are shared between snapshots.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct SnapshotStore {
+    pub(crate) snapshots: Vec<Snapshot>,
+}
+
+impl SnapshotStore {
+    /// Add the state of the database at the given commit.
+    /// Commits are expected to be pushed from the oldest
+    /// to the newest.
+    pub(crate) fn push(&mut self, commit: Commit, database: &DataBase) {
+        let mut previous_games: HashMap<u64, &Arc<Game>> = HashMap::new();
+        let mut previous_chunks: HashMap<u64, &Chunk> = HashMap::new();
+        if let Some(previous) = self.snapshots.last() {
+            for chunk in &previous.chunks {
+                if let Some(first) = chunk.first() {
+                    previous_chunks.insert(first.uuid, chunk);
+                }
+                for game in chunk.iter() {
+                    previous_games.insert(game.uuid, game);
+                }
+            }
+        }
+        let mut chunks: Vec<Chunk> = Vec::new();
+        let mut current: Vec<Arc<Game>> = Vec::new();
+        for mut game in database.get_all_games().items {
+            // the id depends on the position in the database
+            // and would prevent sharing games between snapshots
+            game.id = 0;
+            let game = match previous_games.get(&game.uuid) {
+                Some(&previous) if **previous == game => previous.clone(),
+                _ => Arc::new(game),
+            };
+            let boundary = game.uuid % CHUNK_FACTOR == 0;
+            current.push(game);
+            if boundary {
+                chunks.push(share_chunk(&previous_chunks, current));
+                current = Vec::new();
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(share_chunk(&previous_chunks, current));
+        }
+        self.snapshots.push(Snapshot { commit, chunks });
+    }
+    /// Return all snapshots, from the oldest to the newest.
+    pub fn get_all_snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+    /// Return the last snapshot committed on or before the
+    /// given date, if any.
+    pub fn get_snapshot_as_of(&self, date: NaiveDate) -> Option<&Snapshot> {
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot.commit.date.date_naive() <= date)
+    }
+}
+
+// Return the chunk of the previous snapshot if it holds
+// exactly the same games, a new chunk otherwise.
+fn share_chunk(previous_chunks: &HashMap<u64, &Chunk>, games: Vec<Arc<Game>>) -> Chunk {
+    if let Some(&previous) = previous_chunks.get(&games[0].uuid) {
+        if previous.len() == games.len()
+            && previous
+                .iter()
+                .zip(games.iter())
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+        {
+            return previous.clone();
+        }
+    }
+    Arc::new(games)
+}
+
+#[cfg(test)]
+mod test_snapshot_store {
+    use super::*;
+    use crate::DataBaseBuilder;
+    use chrono::DateTime;
+    fn commit(id: &str, date: &str) -> Commit {
+        Commit {
+            id: id.to_string(),
+            author: "tester".to_string(),
+            date: DateTime::parse_from_rfc3339(date).unwrap(),
+        }
+    }
+    fn database(count: usize) -> DataBase {
+        let mut data = String::new();
+        for i in 0..count {
+            data.push_str(&format!("Game\tgame {}\nEngine\tengine {}\n", i, i % 3));
+        }
+        DataBaseBuilder::new(false, false).build_from_string(data)
+    }
+    #[test]
+    fn as_of() {
+        let mut store = SnapshotStore::default();
+        store.push(commit("1", "2021-06-01T12:00:00+02:00"), &database(10));
+        store.push(commit("2", "2022-06-01T12:00:00+02:00"), &database(20));
+        assert!(store
+            .get_snapshot_as_of(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
+            .is_none());
+        let snapshot = store
+            .get_snapshot_as_of(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap())
+            .unwrap();
+        assert_eq!(snapshot.commit.id, "1");
+        let db = snapshot.to_database();
+        assert_eq!(db, database(10));
+        let snapshot = store
+            .get_snapshot_as_of(NaiveDate::from_ymd_opt(2022, 6, 1).unwrap())
+            .unwrap();
+        assert_eq!(snapshot.to_database(), database(20));
+    }
+    #[test]
+    fn chunks_are_shared() {
+        let mut store = SnapshotStore::default();
+        store.push(commit("1", "2021-06-01T12:00:00+02:00"), &database(200));
+        store.push(commit("2", "2022-06-01T12:00:00+02:00"), &database(200));
+        let snapshots = store.get_all_snapshots();
+        assert!(snapshots[0].chunks.len() > 1);
+        for (a, b) in snapshots[0].chunks.iter().zip(snapshots[1].chunks.iter()) {
+            assert!(Arc::ptr_eq(a, b));
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Export of the database to SQLite (feature `sqlite`).
+//!
+//! The schema is normalised: the games are stored in the
+//! `games` table and each item collection in its own table
+//! (`engines`, `runtimes`, `genres`, `tags`, `devs`,
+//! `publishers`), linked to the games by a join table
+//! (`game_engines`, `game_runtimes`...) mirroring `Item.games`.
+//! The stores of each game are listed in the `stores` table.
+//!
+//! Two views are provided for convenience:
+//! - `game_details`: one row per game with the names of its
+//!   items, multiple values being joined by ", ",
+//! - `item_counts`: the number of games of each item, the
+//!   `collection` column giving the table of the item.
+//!
+//! The version of the schema is stored in the `user_version`
+//! pragma and must be increased whenever the schema changes.
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+use crate::collections::{DataBase, QueryResult};
+use crate::models::{Item, Store};
+
+/// Version of the SQLite schema.
+pub const SQLITE_SCHEMA_VERSION: i32 = 1;
+
+type Items = for<'a> fn(&'a DataBase) -> QueryResult<&'a Item>;
+
+// Item collections, as (table, join table, column, items)
+// with the join table column referencing the item.
+const ITEM_TABLES: [(&str, &str, &str, Items); 6] = [
+    (
+        "engines",
+        "game_engines",
+        "engine_id",
+        DataBase::get_all_engines,
+    ),
+    (
+        "runtimes",
+        "game_runtimes",
+        "runtime_id",
+        DataBase::get_all_runtimes,
+    ),
+    (
+        "genres",
+        "game_genres",
+        "genre_id",
+        DataBase::get_all_genres,
+    ),
+    ("tags", "game_tags", "tag_id", DataBase::get_all_tags),
+    ("devs", "game_devs", "dev_id", DataBase::get_all_devs),
+    (
+        "publishers",
+        "game_publishers",
+        "publisher_id",
+        DataBase::get_all_publis,
+    ),
+];
+
+/// SQL statements creating the tables, indices and views
+/// of the schema (the item tables excepted).
+pub const SQLITE_SCHEMA: &str = "
+CREATE TABLE games (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    cover TEXT,
+    setup TEXT,
+    hints TEXT,
+    year TEXT,
+    version TEXT,
+    status TEXT,
+    added TEXT,
+    updated TEXT
+);
+CREATE INDEX games_name ON games(name);
+CREATE INDEX games_year ON games(year);
+CREATE TABLE stores (
+    game_id INTEGER NOT NULL REFERENCES games(id),
+    position INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    url TEXT NOT NULL,
+    PRIMARY KEY (game_id, position)
+);
+CREATE INDEX stores_kind ON stores(kind);
+";
+
+// Item table and its join table with the games.
+fn item_tables_sql(table: &str, join: &str, column: &str) -> String {
+    format!(
+        "CREATE TABLE {table} (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+CREATE TABLE {join} (
+    game_id INTEGER NOT NULL REFERENCES games(id),
+    {column} INTEGER NOT NULL REFERENCES {table}(id),
+    PRIMARY KEY (game_id, {column})
+);
+CREATE INDEX {join}_{column} ON {join}({column});
+",
+    )
+}
+
+// Views built on top of the item tables.
+fn views_sql() -> String {
+    let details: Vec<String> = ITEM_TABLES
+        .iter()
+        .map(|(table, join, column, _)| {
+            format!(
+                "(SELECT group_concat(name, ', ') FROM (SELECT i.name FROM {join} j \
+                 JOIN {table} i ON i.id = j.{column} WHERE j.game_id = g.id ORDER BY i.name)) \
+                 AS {table}"
+            )
+        })
+        .collect();
+    let counts: Vec<String> = ITEM_TABLES
+        .iter()
+        .map(|(table, join, column, _)| {
+            format!(
+                "SELECT '{table}' AS collection, i.name AS name, count(j.game_id) AS games \
+                 FROM {table} i LEFT JOIN {join} j ON j.{column} = i.id GROUP BY i.id"
+            )
+        })
+        .collect();
+    format!(
+        "CREATE VIEW game_details AS SELECT g.*, {} FROM games g;\n\
+         CREATE VIEW item_counts AS {};\n",
+        details.join(", "),
+        counts.join(" UNION ALL ")
+    )
+}
+
+impl DataBase {
+    /// Write the database in the given SQLite database, which
+    /// is expected to be empty. Everything is written in a
+    /// single transaction.
+    pub fn write_sqlite(&self, connection: &mut Connection) -> rusqlite::Result<()> {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(SQLITE_SCHEMA)?;
+        for (table, join, column, _) in ITEM_TABLES {
+            transaction.execute_batch(&item_tables_sql(table, join, column))?;
+        }
+        transaction.execute_batch(&views_sql())?;
+        transaction.pragma_update(None, "user_version", SQLITE_SCHEMA_VERSION)?;
+        let games = self.get_all_games().items;
+        {
+            let mut insert_game = transaction.prepare(
+                "INSERT INTO games (id, name, cover, setup, hints, year, version, status, added, updated) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            let mut insert_store = transaction.prepare(
+                "INSERT INTO stores (game_id, position, kind, url) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for game in &games {
+                insert_game.execute(params![
+                    game.id as i64,
+                    game.name,
+                    game.cover,
+                    game.setup,
+                    game.hints,
+                    game.year.as_deref(),
+                    game.version,
+                    game.status,
+                    game.added.map(|date| date.format("%F").to_string()),
+                    game.updated.map(|date| date.format("%F").to_string()),
+                ])?;
+                for (position, url) in game.stores.iter().flatten().enumerate() {
+                    insert_store.execute(params![
+                        game.id as i64,
+                        position as i64 + 1,
+                        Store::new(url).kind(),
+                        url
+                    ])?;
+                }
+            }
+        }
+        for (table, join, column, items) in ITEM_TABLES {
+            let mut insert_item =
+                transaction.prepare(&format!("INSERT INTO {table} (id, name) VALUES (?1, ?2)"))?;
+            let mut insert_link = transaction.prepare(&format!(
+                "INSERT OR IGNORE INTO {join} (game_id, {column}) VALUES (?1, ?2)"
+            ))?;
+            for (index, item) in items(self).items.iter().enumerate() {
+                let item_id = index as i64 + 1;
+                insert_item.execute(params![item_id, item.name.as_str()])?;
+                for uuid in &item.games {
+                    if let Some(game) = self.games.get(uuid) {
+                        insert_link.execute(params![game.id as i64, item_id])?;
+                    }
+                }
+            }
+        }
+        transaction.commit()
+    }
+    /// Write the database in a new SQLite file. Writing in
+    /// an existing SQLite database fails since the tables
+    /// already exist.
+    pub fn to_sqlite<P: AsRef<Path>>(&self, path: P) -> rusqlite::Result<()> {
+        let mut connection = Connection::open(path)?;
+        self.write_sqlite(&mut connection)
+    }
+}
+
+#[cfg(test)]
+mod test_sqlite {
+    use super::*;
+    use crate::models::{Game, Symbol};
+    fn database() -> DataBase {
+        let game = |id: usize, name: &str, engine: &str, tags: &[&str]| Game {
+            id,
+            uuid: Game::compute_uuid(name),
+            name: name.to_string(),
+            engine: Some(engine.into()),
+            tags: Some(tags.iter().map(|tag| Symbol::from(*tag)).collect()),
+            stores: Some(vec!["https://store.steampowered.com/app/1".to_string()]),
+            ..Default::default()
+        };
+        DataBase::from_games(vec![
+            game(1, "Shuggy", "FNA", &["indie", "puzzle"]),
+            game(2, "Owlboy", "FNA", &["indie"]),
+            game(3, "Cataclysm", "C++", &[]),
+        ])
+    }
+    #[test]
+    fn export() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        database().write_sqlite(&mut connection).unwrap();
+        let version: i32 = connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SQLITE_SCHEMA_VERSION);
+        let fna: i64 = connection
+            .query_row(
+                "SELECT games FROM item_counts WHERE collection = 'engines' AND name = 'FNA'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(fna, 2);
+        let (engines, tags): (String, String) = connection
+            .query_row(
+                "SELECT engines, tags FROM game_details WHERE name = 'Shuggy'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(engines, "FNA");
+        assert_eq!(tags, "indie, puzzle");
+        let kind: String = connection
+            .query_row("SELECT kind FROM stores WHERE game_id = 3", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(kind, "Steam");
+    }
+}
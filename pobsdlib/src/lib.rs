@@ -6,18 +6,39 @@
 //!
 #[macro_use]
 extern crate serde_derive;
+extern crate bincode;
 extern crate chrono;
+extern crate csv;
 extern crate reqwest;
+#[cfg(feature = "sqlite")]
+extern crate rusqlite;
+extern crate schemars;
+extern crate serde;
 extern crate serde_json;
+#[cfg(feature = "async")]
+extern crate tokio;
 // public api
 pub mod collections;
 pub mod models;
+pub mod record;
 pub mod utils;
 
+pub use crate::collections::borrowed_database::BorrowedDataBase;
 pub use crate::collections::database::DataBase;
+pub use crate::collections::history::{History, HistoryEvent};
 pub use crate::collections::query_result::QueryResult;
+pub use crate::collections::snapshot_store::{Snapshot, SnapshotStore};
+pub use crate::models::commit::Commit;
 pub use crate::models::field::Field;
+pub use crate::models::field_kind::FieldKind;
 pub use crate::models::game::Game;
+pub use crate::models::game_change::{ChangeKind, FieldChange, GameChange};
 pub use crate::models::game_filter::GameFilter;
+pub use crate::models::game_ref::GameRef;
 pub use crate::models::item::Item;
+pub use crate::models::lint_config::LintConfig;
+pub use crate::models::normalizer::Normalizer;
+pub use crate::models::symbol::Symbol;
+pub use crate::models::vocabulary::Vocabulary;
 pub use crate::utils::database_builder::DataBaseBuilder;
+pub use crate::utils::history_builder::HistoryBuilder;
@@ -0,0 +1,13 @@
+use chrono::{DateTime, FixedOffset};
+
+/// A Commit represents a revision of the database
+/// in a git repository.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Commit {
+    /// hash of the commit.
+    pub id: String,
+    /// name of the author of the commit.
+    pub author: String,
+    /// date of the commit (author date).
+    pub date: DateTime<FixedOffset>,
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+/// Severity of a lint rule.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The rule is enforced.
+    Error,
+    /// The rule is reported but not enforced.
+    Warning,
+    /// The rule is not checked.
+    Off,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Off => write!(f, "off"),
+        }
+    }
+}
+
+/// A Diagnostic is a problem found by the linter
+/// in the database.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// name of the rule.
+    pub rule: String,
+    /// severity of the rule.
+    pub severity: Severity,
+    /// line of the database (starting at 1).
+    pub line: usize,
+    /// name of the game concerned, if any.
+    pub game: Option<String>,
+    /// description of the problem.
+    pub message: String,
+}
+
+/// Display the diagnostic as "line: severity[rule]: message".
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}[{}]: {}",
+            self.line, self.severity, self.rule, self.message
+        )
+    }
+}
+
+// Escape the message of a GitHub workflow command.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+// Escape a property of a GitHub workflow command.
+fn escape_property(property: &str) -> String {
+    escape_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+impl Diagnostic {
+    /// Format the diagnostic as a GitHub workflow command
+    /// so that it is shown as an annotation of the file.
+    pub fn to_github_annotation(&self, filename: &str) -> String {
+        format!(
+            "::{} file={},line={},title={}::{}",
+            self.severity,
+            escape_property(filename),
+            self.line,
+            escape_property(&self.rule),
+            escape_data(&self.message)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_diagnostic {
+    use super::*;
+    #[test]
+    fn github_annotation() {
+        let diagnostic = Diagnostic {
+            rule: "store-url".to_string(),
+            severity: Severity::Error,
+            line: 3,
+            game: None,
+            message: "100% invalid,\nreally".to_string(),
+        };
+        assert_eq!(
+            diagnostic.to_github_annotation("db/a:b,c.db"),
+            "::error file=db/a%3Ab%2Cc.db,line=3,title=store-url::100%25 invalid,%0Areally"
+        );
+    }
+}
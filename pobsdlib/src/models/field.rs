@@ -151,6 +151,10 @@ impl<'a> Field<'a> {
     pub fn from(line: &'a str) -> Self {
         // Split the line in a left and right hand sides
         let (left, right) = split_line(line);
+        // Surrounding whitespaces are not part of the value
+        let right = right
+            .map(|right| right.trim())
+            .filter(|right| !right.is_empty());
         // Use the left hand side to discriminate between single and multiple item lines
         if let Some(left) = left {
             match left {
@@ -199,7 +203,10 @@ impl<'a> Field<'a> {
                     Some(right) => {
                         let mut items: Vec<&str> = Vec::new();
                         for item in right.split(' ') {
-                            items.push(item.trim());
+                            // skip empty items (e.g. double separators)
+                            if !item.trim().is_empty() {
+                                items.push(item.trim());
+                            }
                         }
                         Field::Store(Some(items))
                     }
@@ -209,7 +216,10 @@ impl<'a> Field<'a> {
                     Some(right) => {
                         let mut items: Vec<&str> = Vec::new();
                         for item in right.split(',') {
-                            items.push(item.trim());
+                            // skip empty items (e.g. double separators)
+                            if !item.trim().is_empty() {
+                                items.push(item.trim());
+                            }
                         }
                         Field::Genres(Some(items))
                     }
@@ -219,7 +229,10 @@ impl<'a> Field<'a> {
                     Some(right) => {
                         let mut items: Vec<&str> = Vec::new();
                         for item in right.split(',') {
-                            items.push(item.trim());
+                            // skip empty items (e.g. double separators)
+                            if !item.trim().is_empty() {
+                                items.push(item.trim());
+                            }
                         }
                         Field::Tags(Some(items))
                     }
@@ -231,7 +244,7 @@ impl<'a> Field<'a> {
                 },
                 "Added" => match right {
                     Some(right) => Field::Added(Some(right)),
-                    None => Field::Added(Some("1970/01/01")),
+                    None => Field::Added(None),
                 },
                 "Updated" => match right {
                     Some(right) => Field::Updated(Some(right)),
@@ -406,6 +419,17 @@ mod test_methods {
         assert_eq!(format!("{}", field), input);
     }
     #[test]
+    fn from_line_with_extra_whitespaces() {
+        let field = Field::from("Engine\t FNA ");
+        assert_eq!(Field::Engine(Some("FNA")), field);
+        let field = Field::from("Engine\t ");
+        assert_eq!(Field::Engine(None), field);
+        let field = Field::from("Tags\tfirst,, second ");
+        assert_eq!(Field::Tags(Some(vec!["first", "second"])), field);
+        let field = Field::from("Store\tfirst  second");
+        assert_eq!(Field::Store(Some(vec!["first", "second"])), field);
+    }
+    #[test]
     fn from_malformed_line() {
         let input = "Let's not\tpanic";
         let field = Field::from(&input);
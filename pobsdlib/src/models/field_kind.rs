@@ -0,0 +1,154 @@
+use schemars::JsonSchema;
+
+use crate::models::{Game, GameRef, Store, Symbol};
+
+/// Kinds of the facets of a game, i.e. the fields (or the
+/// values derived from a field) indexed by the item
+/// collections of the database.
+///
+/// The engine, runtime, genres, tags, year, developer and
+/// publisher are always indexed. The store kinds (Steam,
+/// GOG...), the status (without its date) and the setup tool
+/// (the first word of the setup) are derived from the game
+/// and only indexed once registered (see
+/// DataBase::register_facet).
+#[derive(
+    Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+pub enum FieldKind {
+    #[serde(rename = "engines")]
+    Engine,
+    #[serde(rename = "runtimes")]
+    Runtime,
+    #[serde(rename = "genres")]
+    Genre,
+    #[serde(rename = "tags")]
+    Tag,
+    #[serde(rename = "years")]
+    Year,
+    #[serde(rename = "devs")]
+    Dev,
+    #[serde(rename = "publis")]
+    Publi,
+    #[serde(rename = "stores")]
+    Store,
+    #[serde(rename = "statuses")]
+    Status,
+    #[serde(rename = "setups")]
+    Setup,
+}
+
+impl FieldKind {
+    /// Facets indexed by every database.
+    pub const DEFAULT: [FieldKind; 7] = [
+        FieldKind::Engine,
+        FieldKind::Runtime,
+        FieldKind::Genre,
+        FieldKind::Tag,
+        FieldKind::Year,
+        FieldKind::Dev,
+        FieldKind::Publi,
+    ];
+    /// All the facets.
+    pub const ALL: [FieldKind; 10] = [
+        FieldKind::Engine,
+        FieldKind::Runtime,
+        FieldKind::Genre,
+        FieldKind::Tag,
+        FieldKind::Year,
+        FieldKind::Dev,
+        FieldKind::Publi,
+        FieldKind::Store,
+        FieldKind::Status,
+        FieldKind::Setup,
+    ];
+    /// Return true if the values of the facet are derived from
+    /// a field of the game rather than being the field itself.
+    pub fn is_derived(&self) -> bool {
+        matches!(
+            self,
+            FieldKind::Store | FieldKind::Status | FieldKind::Setup
+        )
+    }
+    /// Return the values of the facet for the given game.
+    pub fn values<'a>(&self, game: &'a Game) -> Vec<&'a str> {
+        match self {
+            FieldKind::Store => game
+                .stores
+                .iter()
+                .flatten()
+                .map(|url| Store::new(url).kind())
+                .collect(),
+            FieldKind::Status => game
+                .status
+                .iter()
+                .map(|status| status.split(" (").next().unwrap_or_default().trim())
+                .filter(|status| !status.is_empty())
+                .collect(),
+            FieldKind::Setup => game
+                .setup
+                .iter()
+                .filter_map(|setup| setup.split_whitespace().next())
+                .collect(),
+            FieldKind::Engine => game.engine.as_deref().into_iter().collect(),
+            FieldKind::Runtime => game.runtime.as_deref().into_iter().collect(),
+            FieldKind::Genre => game.genres.iter().flatten().map(Symbol::as_str).collect(),
+            FieldKind::Tag => game.tags.iter().flatten().map(Symbol::as_str).collect(),
+            FieldKind::Year => game.year.as_deref().into_iter().collect(),
+            FieldKind::Dev => game.dev.as_deref().into_iter().collect(),
+            FieldKind::Publi => game.publi.as_deref().into_iter().collect(),
+        }
+    }
+    // Return the symbols of a facet which is not derived, so
+    // that the database can replace them by the ones of its
+    // items.
+    pub(crate) fn symbols_mut<'a>(&self, game: &'a mut Game) -> Vec<&'a mut Symbol> {
+        match self {
+            FieldKind::Engine => game.engine.iter_mut().collect(),
+            FieldKind::Runtime => game.runtime.iter_mut().collect(),
+            FieldKind::Genre => game.genres.iter_mut().flatten().collect(),
+            FieldKind::Tag => game.tags.iter_mut().flatten().collect(),
+            FieldKind::Year => game.year.iter_mut().collect(),
+            FieldKind::Dev => game.dev.iter_mut().collect(),
+            FieldKind::Publi => game.publi.iter_mut().collect(),
+            _ => Vec::new(),
+        }
+    }
+    // Same as symbols_mut for a borrowed game, so that the
+    // borrowed database can intern its values.
+    pub(crate) fn slices_mut<'a, 'b>(&self, game: &'b mut GameRef<'a>) -> Vec<&'b mut &'a str> {
+        match self {
+            FieldKind::Engine => game.engine.iter_mut().collect(),
+            FieldKind::Runtime => game.runtime.iter_mut().collect(),
+            FieldKind::Genre => game.genres.iter_mut().flatten().collect(),
+            FieldKind::Tag => game.tags.iter_mut().flatten().collect(),
+            FieldKind::Year => game.year.iter_mut().collect(),
+            FieldKind::Dev => game.dev.iter_mut().collect(),
+            FieldKind::Publi => game.publi.iter_mut().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_field_kind {
+    use super::*;
+    #[test]
+    fn values() {
+        let game = Game {
+            genres: Some(vec!["Puzzle".into(), "Platformer".into()]),
+            stores: Some(vec![
+                "https://store.steampowered.com/app/1".to_string(),
+                "https://www.gog.com/game/shuggy".to_string(),
+            ]),
+            status: Some("runs (2021-08-21)".to_string()),
+            setup: Some("fnaify -y".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(FieldKind::Genre.values(&game), vec!["Puzzle", "Platformer"]);
+        assert_eq!(FieldKind::Store.values(&game), vec!["Steam", "GOG"]);
+        assert_eq!(FieldKind::Status.values(&game), vec!["runs"]);
+        assert_eq!(FieldKind::Setup.values(&game), vec!["fnaify"]);
+        assert!(FieldKind::Engine.values(&game).is_empty());
+    }
+}
@@ -1,6 +1,11 @@
 use chrono::NaiveDate;
+use schemars::JsonSchema;
 use std::cmp::{Ordering, PartialOrd};
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::models::Symbol;
 
 /// # Represent a game
 /// The Game struct represents a game from the database
@@ -16,7 +21,7 @@ use std::fmt;
 /// A String type is used for Year since sometimes the
 /// release date can only be described by textw (e.g.
 /// "early acess").
-#[derive(Serialize, Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default, Debug, PartialEq, Eq)]
 pub struct Game {
     /// The id of the game.
     pub id: usize,
@@ -27,26 +32,26 @@ pub struct Game {
     /// The cover of the game.
     pub cover: Option<String>,
     /// The engine used by the game.
-    pub engine: Option<String>,
+    pub engine: Option<Symbol>,
     /// Step(s) to setup the game.
     pub setup: Option<String>,
     /// The executable in the package.
-    pub runtime: Option<String>,
+    pub runtime: Option<Symbol>,
     /// A vector with store urls.
     pub stores: Option<Vec<String>>,
     /// Hints (as the name imply).
     pub hints: Option<String>,
     /// A vector of genres associated with the game.
-    pub genres: Option<Vec<String>>,
+    pub genres: Option<Vec<Symbol>>,
     /// A vector of tags associated with the game.
-    pub tags: Option<Vec<String>>,
+    pub tags: Option<Vec<Symbol>>,
     /// Released year (can be text such as "early access".
-    pub year: Option<String>,
+    pub year: Option<Symbol>,
     /// Developer.
-    pub dev: Option<String>,
+    pub dev: Option<Symbol>,
     /// Publisher.
     #[serde(rename = "pub")]
-    pub publi: Option<String>,
+    pub publi: Option<Symbol>,
     /// Version of the game.
     pub version: Option<String>,
     /// When tested on -current.
@@ -169,6 +174,57 @@ impl fmt::Display for Game {
 }
 
 impl Game {
+    /// Return the uuid of a game with the given name.
+    pub fn compute_uuid(name: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        hasher.finish()
+    }
+    /// Return the key used to sort the games by name in the
+    /// database: the name in lower case, without its leading
+    /// "The " or "A ".
+    pub fn sort_key(name: &str) -> String {
+        let name = name.trim().to_lowercase();
+        for article in ["the ", "a "] {
+            if let Some(rest) = name.strip_prefix(article) {
+                return rest.to_string();
+            }
+        }
+        name
+    }
+    /// Return the key and the value of each line describing
+    /// the game in the database, in the database order.
+    /// Multiple items (stores, genres, tags) are joined using
+    /// the separator of the database.
+    pub fn field_values(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("Game", Some(self.name.clone())),
+            ("Cover", self.cover.clone()),
+            ("Engine", self.engine.as_deref().map(String::from)),
+            ("Setup", self.setup.clone()),
+            ("Runtime", self.runtime.as_deref().map(String::from)),
+            ("Store", self.stores.as_ref().map(|stores| stores.join(" "))),
+            ("Hints", self.hints.clone()),
+            (
+                "Genre",
+                self.genres.as_ref().map(|genres| genres.join(", ")),
+            ),
+            ("Tags", self.tags.as_ref().map(|tags| tags.join(", "))),
+            ("Year", self.year.as_deref().map(String::from)),
+            ("Dev", self.dev.as_deref().map(String::from)),
+            ("Pub", self.publi.as_deref().map(String::from)),
+            ("Version", self.version.clone()),
+            ("Status", self.status.clone()),
+            (
+                "Added",
+                self.added.map(|added| added.format("%F").to_string()),
+            ),
+            (
+                "Updated",
+                self.updated.map(|updated| updated.format("%F").to_string()),
+            ),
+        ]
+    }
     /// Return true if the name of the game contains the
     /// given pattern, false otherwise. It is not case
     /// sensitive.
@@ -312,21 +368,21 @@ mod test_game_methods {
     use super::*;
     fn create_game() -> Game {
         let mut game = Game::default();
-        let tags: Vec<String> = vec!["tag1".to_string(), "tag2".to_string()];
-        let genres: Vec<String> = vec!["genre1".to_string(), "genre2".to_string()];
+        let tags: Vec<Symbol> = vec!["tag1".into(), "tag2".into()];
+        let genres: Vec<Symbol> = vec!["genre1".into(), "genre2".into()];
         let stores: Vec<String> = vec!["store1".to_string(), "store2".to_string()];
         game.name = "game name".to_string();
         game.cover = Some("cover.jpg".to_string());
-        game.engine = Some("game engine".to_string());
+        game.engine = Some("game engine".into());
         game.setup = Some("game setup".to_string());
-        game.runtime = Some("game runtime".to_string());
+        game.runtime = Some("game runtime".into());
         game.stores = Some(stores);
         game.hints = Some("game hints".to_string());
         game.genres = Some(genres);
         game.tags = Some(tags);
-        game.year = Some("1980".to_string());
-        game.dev = Some("game dev".to_string());
-        game.publi = Some("game publi".to_string());
+        game.year = Some("1980".into());
+        game.dev = Some("game dev".into());
+        game.publi = Some("game publi".into());
         game.version = Some("game version".to_string());
         game.status = Some("game status".to_string());
         game.added = Some(
@@ -434,6 +490,16 @@ mod test_game_methods {
         assert!(game1.lt(&game2));
     }
     #[test]
+    fn test_sort_key() {
+        assert_eq!(
+            Game::sort_key("The Adventures of Shuggy"),
+            "adventures of shuggy"
+        );
+        assert_eq!(Game::sort_key("A Golden Wake"), "golden wake");
+        assert_eq!(Game::sort_key("Aedemphia"), "aedemphia");
+        assert_eq!(Game::sort_key("Theatre"), "theatre");
+    }
+    #[test]
     fn test_display() {
         let game_str = "Game\tAaaaaAAaaaAAAaaAAAAaAAAAA!!! for the Awesome
 Cover\tAaaaaA_for_the_Awesome_Cover.jpg
@@ -458,7 +524,7 @@ Updated";
             cover: Some("AaaaaA_for_the_Awesome_Cover.jpg".to_string()),
             engine: None,
             setup: None,
-            runtime: Some("HumblePlay".to_string()),
+            runtime: Some("HumblePlay".into()),
             stores: Some(vec![
                 "https://www.humblebundle.com/store/aaaaaaaaaaaaaaaaaaaaaaaaa-for-the-awesome"
                     .to_string(),
@@ -466,7 +532,7 @@ Updated";
             hints: Some("Demo on HumbleBundle store page".to_string()),
             genres: None,
             tags: None,
-            year: Some("2011".to_string()),
+            year: Some("2011".into()),
             dev: None,
             publi: None,
             version: None,
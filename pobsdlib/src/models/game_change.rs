@@ -0,0 +1,200 @@
+use crate::models::Game;
+use std::fmt;
+
+/// A FieldChange represents the modification of
+/// one line of a game between two versions of the
+/// database. The field is named after the key used
+/// in the database (e.g. "Engine" or "Tags").
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct FieldChange {
+    /// key of the modified line.
+    pub field: String,
+    /// value before the modification.
+    pub old: Option<String>,
+    /// value after the modification.
+    pub new: Option<String>,
+}
+
+/// The kind of change undergone by a game.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The game has been added to the database.
+    Added,
+    /// The game has been removed from the database.
+    Removed,
+    /// Some fields of the game have been modified.
+    Modified(Vec<FieldChange>),
+}
+
+/// A GameChange describes how a game changed between
+/// two versions of the database. Games are identified
+/// by their uuid, which is derived from their name.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameChange {
+    /// uuid of the game.
+    pub uuid: u64,
+    /// name of the game.
+    pub name: String,
+    /// what happened to the game.
+    pub kind: ChangeKind,
+}
+
+// Separator of the items of the multiple item lines.
+fn item_separator(field: &str) -> Option<&'static str> {
+    match field {
+        "Store" => Some(" "),
+        "Genre" | "Tags" => Some(", "),
+        _ => None,
+    }
+}
+
+/// Display the change as "Engine XNA → FNA" for single item
+/// lines and as "Tags +roguelike -indie" for multiple item lines.
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match item_separator(&self.field) {
+            Some(separator) => {
+                let split = |value: &Option<String>| -> Vec<String> {
+                    match value {
+                        Some(value) => value
+                            .split(separator)
+                            .map(|item| item.to_string())
+                            .collect(),
+                        None => Vec::new(),
+                    }
+                };
+                let old = split(&self.old);
+                let new = split(&self.new);
+                let mut items: Vec<String> = Vec::new();
+                for item in &new {
+                    if !old.contains(item) {
+                        items.push(format!("+{}", item));
+                    }
+                }
+                for item in &old {
+                    if !new.contains(item) {
+                        items.push(format!("-{}", item));
+                    }
+                }
+                // only the order of the items changed
+                if items.is_empty() {
+                    items.push("reordered".to_string());
+                }
+                write!(f, "{} {}", self.field, items.join(" "))
+            }
+            None => write!(
+                f,
+                "{} {} → {}",
+                self.field,
+                self.old.as_deref().unwrap_or("(none)"),
+                self.new.as_deref().unwrap_or("(none)")
+            ),
+        }
+    }
+}
+
+/// Display the change as
+/// "Game 'Aeternum': Engine XNA → FNA, Tags +roguelike".
+impl fmt::Display for GameChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ChangeKind::Added => write!(f, "Game '{}': added", self.name),
+            ChangeKind::Removed => write!(f, "Game '{}': removed", self.name),
+            ChangeKind::Modified(fields) => {
+                let fields: Vec<String> = fields.iter().map(|field| field.to_string()).collect();
+                write!(f, "Game '{}': {}", self.name, fields.join(", "))
+            }
+        }
+    }
+}
+
+impl FieldChange {
+    /// Return the list of the lines that differ between
+    /// two versions of the same game, in the database order.
+    pub fn between(old: &Game, new: &Game) -> Vec<FieldChange> {
+        let mut changes: Vec<FieldChange> = Vec::new();
+        for ((field, old), (_, new)) in old.field_values().into_iter().zip(new.field_values()) {
+            if old != new {
+                changes.push(FieldChange {
+                    field: field.to_string(),
+                    old,
+                    new,
+                });
+            }
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod test_field_change {
+    use super::*;
+    #[test]
+    fn between_same_game() {
+        let game = Game {
+            name: "game".to_string(),
+            ..Default::default()
+        };
+        assert!(FieldChange::between(&game, &game.clone()).is_empty());
+    }
+    #[test]
+    fn between_modified_game() {
+        let old = Game {
+            name: "game".to_string(),
+            engine: Some("XNA".into()),
+            ..Default::default()
+        };
+        let mut new = old.clone();
+        new.engine = Some("FNA".into());
+        new.tags = Some(vec!["indie".into(), "roguelike".into()]);
+        assert_eq!(
+            FieldChange::between(&old, &new),
+            vec![
+                FieldChange {
+                    field: "Engine".to_string(),
+                    old: Some("XNA".to_string()),
+                    new: Some("FNA".to_string()),
+                },
+                FieldChange {
+                    field: "Tags".to_string(),
+                    old: None,
+                    new: Some("indie, roguelike".to_string()),
+                },
+            ]
+        );
+    }
+    #[test]
+    fn display() {
+        let change = GameChange {
+            uuid: 0,
+            name: "Aeternum".to_string(),
+            kind: ChangeKind::Modified(vec![
+                FieldChange {
+                    field: "Engine".to_string(),
+                    old: Some("XNA".to_string()),
+                    new: Some("FNA".to_string()),
+                },
+                FieldChange {
+                    field: "Tags".to_string(),
+                    old: Some("indie".to_string()),
+                    new: Some("indie, roguelike".to_string()),
+                },
+                FieldChange {
+                    field: "Year".to_string(),
+                    old: Some("2017".to_string()),
+                    new: None,
+                },
+            ]),
+        };
+        assert_eq!(
+            format!("{}", change),
+            "Game 'Aeternum': Engine XNA → FNA, Tags +roguelike, Year 2017 → (none)"
+        );
+        let change = GameChange {
+            uuid: 0,
+            name: "Aeternum".to_string(),
+            kind: ChangeKind::Added,
+        };
+        assert_eq!(format!("{}", change), "Game 'Aeternum': added");
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+use std::cmp::{Ordering, PartialOrd};
+
+use crate::models::{Game, Symbol};
+
+/// # Represent a borrowed game
+/// The GameRef struct holds the same fields as Game
+/// but its values are slices of the text of the database
+/// it was read from (see BorrowedDataBase).
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct GameRef<'a> {
+    /// The id of the game.
+    pub id: usize,
+    /// The unique id
+    pub uuid: u64,
+    /// The name of the game.
+    pub name: &'a str,
+    /// The cover of the game.
+    pub cover: Option<&'a str>,
+    /// The engine used by the game.
+    pub engine: Option<&'a str>,
+    /// Step(s) to setup the game.
+    pub setup: Option<&'a str>,
+    /// The executable in the package.
+    pub runtime: Option<&'a str>,
+    /// A vector with store urls.
+    pub stores: Option<Vec<&'a str>>,
+    /// Hints (as the name imply).
+    pub hints: Option<&'a str>,
+    /// A vector of genres associated with the game.
+    pub genres: Option<Vec<&'a str>>,
+    /// A vector of tags associated with the game.
+    pub tags: Option<Vec<&'a str>>,
+    /// Released year (can be text such as "early access".
+    pub year: Option<&'a str>,
+    /// Developer.
+    pub dev: Option<&'a str>,
+    /// Publisher.
+    pub publi: Option<&'a str>,
+    /// Version of the game.
+    pub version: Option<&'a str>,
+    /// When tested on -current.
+    pub status: Option<&'a str>,
+    /// When added
+    pub added: Option<NaiveDate>,
+    /// When updated
+    pub updated: Option<NaiveDate>,
+}
+
+/// As games, borrowed games are ordered by id.
+impl<'a> PartialOrd for GameRef<'a> {
+    fn partial_cmp(&self, other: &GameRef<'a>) -> Option<Ordering> {
+        self.id.partial_cmp(&other.id)
+    }
+}
+
+fn to_string(value: Option<&str>) -> Option<String> {
+    value.map(|value| value.to_string())
+}
+
+fn to_symbol(value: Option<&str>) -> Option<Symbol> {
+    value.map(Symbol::from)
+}
+
+fn to_symbols(values: &Option<Vec<&str>>) -> Option<Vec<Symbol>> {
+    values
+        .as_ref()
+        .map(|values| values.iter().map(|value| Symbol::from(*value)).collect())
+}
+
+fn to_strings(values: &Option<Vec<&str>>) -> Option<Vec<String>> {
+    values
+        .as_ref()
+        .map(|values| values.iter().map(|value| value.to_string()).collect())
+}
+
+impl<'a> GameRef<'a> {
+    /// Return the owned game.
+    pub fn to_game(&self) -> Game {
+        Game {
+            id: self.id,
+            uuid: self.uuid,
+            name: self.name.to_string(),
+            cover: to_string(self.cover),
+            engine: to_symbol(self.engine),
+            setup: to_string(self.setup),
+            runtime: to_symbol(self.runtime),
+            stores: to_strings(&self.stores),
+            hints: to_string(self.hints),
+            genres: to_symbols(&self.genres),
+            tags: to_symbols(&self.tags),
+            year: to_symbol(self.year),
+            dev: to_symbol(self.dev),
+            publi: to_symbol(self.publi),
+            version: to_string(self.version),
+            status: to_string(self.status),
+            added: self.added,
+            updated: self.updated,
+        }
+    }
+}
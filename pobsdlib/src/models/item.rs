@@ -1,19 +1,31 @@
+use schemars::JsonSchema;
 use std::cmp::{Ordering, PartialOrd};
+use std::collections::BTreeSet;
 use std::fmt;
 
+use crate::models::Symbol;
+
 /// Items are attributes to which several games can
 /// be associated with such as genres, tags or years.
 ///
-/// An Item contains the name of the item and a
-/// vector containing the ids of the games associated
-/// to said item.
+/// An Item contains the name of the item and the set
+/// of the uuids of the games associated to said item:
+/// each game is referenced once, in the order of the
+/// uuids.
 ///
-#[derive(Serialize, Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default, Debug, PartialEq, Eq)]
 pub struct Item {
     /// name of the item.
-    pub name: String,
-    /// vector of ids of the games associated to the items.
-    pub games: Vec<u64>,
+    pub name: Symbol,
+    /// uuids of the games associated to the item.
+    pub games: BTreeSet<u64>,
+}
+
+impl Item {
+    /// Return the number of games associated to the item.
+    pub fn count(&self) -> usize {
+        self.games.len()
+    }
 }
 
 impl fmt::Display for Item {
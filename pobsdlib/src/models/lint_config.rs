@@ -0,0 +1,114 @@
+use crate::models::{Severity, Vocabulary};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Rules checked by the linter with their default severity.
+pub const RULES: [(&str, Severity); 16] = [
+    ("missing-name", Severity::Error),
+    ("missing-field", Severity::Error),
+    ("duplicate-field", Severity::Error),
+    ("field-order", Severity::Error),
+    ("unknown-field", Severity::Error),
+    ("invalid-date", Severity::Error),
+    ("date-format", Severity::Error),
+    ("updated-before-added", Severity::Error),
+    ("duplicate-name", Severity::Error),
+    ("unknown-tag", Severity::Warning),
+    ("unknown-genre", Severity::Warning),
+    ("store-url", Severity::Error),
+    ("trailing-whitespace", Severity::Error),
+    ("extra-tab", Severity::Error),
+    ("empty-item", Severity::Error),
+    ("deprecated-term", Severity::Warning),
+];
+
+/// Configuration of the linter, usually read from a
+/// TOML file such as:
+/// ```toml
+/// [rules]
+/// trailing-whitespace = "warning"
+/// field-order = "off"
+///
+/// [vocabulary]
+/// tags = ["indie", "free"]
+/// genres = ["RPG", "Puzzle Platformer"]
+/// ```
+/// The tags and genres are only checked when a
+/// vocabulary is given (see Vocabulary for the
+/// descriptions and deprecated synonyms of the terms).
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct LintConfig {
+    /// severity of the rules, overriding the default ones.
+    pub rules: HashMap<String, Severity>,
+    /// known tags and genres.
+    pub vocabulary: Vocabulary,
+}
+
+impl LintConfig {
+    pub fn new() -> Self {
+        LintConfig::default()
+    }
+    /// Read the configuration from a TOML file.
+    /// Unknown rule names and terms whose parent is not a
+    /// term (see Vocabulary::from_file) are refused.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        LintConfig::from_toml(&content)
+    }
+    fn from_toml(content: &str) -> io::Result<Self> {
+        let config: LintConfig =
+            toml::from_str(content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut unknown: Vec<&str> = config
+            .rules
+            .keys()
+            .map(String::as_str)
+            .filter(|rule| !RULES.iter().any(|(name, _)| name == rule))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort_unstable();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unknown rule(s): {}", unknown.join(", ")),
+            ));
+        }
+        config.vocabulary.check_parents()?;
+        Ok(config)
+    }
+    /// Return the severity of the given rule.
+    pub fn severity(&self, rule: &str) -> Severity {
+        if let Some(severity) = self.rules.get(rule) {
+            return *severity;
+        }
+        RULES
+            .iter()
+            .find(|(name, _)| *name == rule)
+            .map(|(_, severity)| *severity)
+            .unwrap_or(Severity::Error)
+    }
+}
+
+#[cfg(test)]
+mod test_lint_config {
+    use super::*;
+    #[test]
+    fn severity() {
+        let config = LintConfig::from_toml(
+            "[rules]\ntrailing-whitespace = \"off\"\n[vocabulary]\ntags = [\"indie\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.severity("trailing-whitespace"), Severity::Off);
+        assert_eq!(config.severity("unknown-tag"), Severity::Warning);
+        assert_eq!(config.severity("missing-field"), Severity::Error);
+        assert!(config.vocabulary.tags.contains_key("indie"));
+        assert!(config.vocabulary.genres.is_empty());
+    }
+    #[test]
+    fn unknown_rule() {
+        let err = LintConfig::from_toml("[rules]\ntrailing-whitespaces = \"off\"\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "unknown rule(s): trailing-whitespaces");
+    }
+}
@@ -1,11 +1,29 @@
+pub use crate::models::commit::Commit;
+pub use crate::models::diagnostic::{Diagnostic, Severity};
 pub use crate::models::field::Field;
+pub use crate::models::field_kind::FieldKind;
 pub use crate::models::game::Game;
+pub use crate::models::game_change::{ChangeKind, FieldChange, GameChange};
 pub use crate::models::game_filter::GameFilter;
+pub use crate::models::game_ref::GameRef;
 pub use crate::models::item::Item;
+pub use crate::models::lint_config::LintConfig;
+pub use crate::models::normalizer::Normalizer;
 pub use crate::models::store::Store;
+pub use crate::models::symbol::Symbol;
+pub use crate::models::vocabulary::{Term, TermIssue, TermReport, Vocabulary};
 
+pub mod commit;
+pub mod diagnostic;
 pub mod field;
+pub mod field_kind;
 pub mod game;
+pub mod game_change;
 pub mod game_filter;
+pub mod game_ref;
 pub mod item;
+pub mod lint_config;
+pub mod normalizer;
 pub mod store;
+pub mod symbol;
+pub mod vocabulary;
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::models::FieldKind;
+
+// Last words of developers and publishers ignored by the
+// canonical key, so that "Zachtronics, Inc." and "Zachtronics"
+// are the same developer.
+const COMPANY_SUFFIXES: [&str; 8] = [
+    "inc",
+    "llc",
+    "ltd",
+    "limited",
+    "gmbh",
+    "corp",
+    "corporation",
+    "co",
+];
+
+/// Normalisation of the values of the facets, usually read
+/// from a TOML file giving the aliases of canonical values:
+/// ```toml
+/// [tags]
+/// "point and click" = ["pnc", "point & click"]
+///
+/// [devs]
+/// "Zachtronics" = ["Zachtronics Industries"]
+/// ```
+/// The canonical key of a value ignores the case, the dots
+/// and commas, and the dashes and underscores separating
+/// words (as well as the company suffixes of developers and
+/// publishers), so that "Point and Click", "point and click"
+/// and "point-and-click" share the key "point and click". The
+/// aliases then map their key to the one of their canonical
+/// value.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+#[serde(from = "HashMap<FieldKind, HashMap<String, Vec<String>>>")]
+pub struct Normalizer {
+    aliases: HashMap<(FieldKind, String), String>,
+}
+
+impl From<HashMap<FieldKind, HashMap<String, Vec<String>>>> for Normalizer {
+    fn from(table: HashMap<FieldKind, HashMap<String, Vec<String>>>) -> Self {
+        let mut normalizer = Normalizer::new();
+        for (kind, values) in table {
+            for (canonical, aliases) in values {
+                for alias in aliases {
+                    normalizer.add_alias(kind, &alias, &canonical);
+                }
+            }
+        }
+        normalizer
+    }
+}
+
+impl Normalizer {
+    pub fn new() -> Self {
+        Normalizer::default()
+    }
+    /// Read the alias table from a TOML file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+    /// Make the alias a spelling of the canonical value.
+    pub fn add_alias(&mut self, kind: FieldKind, alias: &str, canonical: &str) {
+        self.aliases
+            .insert((kind, normalize(kind, alias)), normalize(kind, canonical));
+    }
+    /// Return the canonical key of the value.
+    pub fn key(&self, kind: FieldKind, value: &str) -> String {
+        let key = (kind, normalize(kind, value));
+        match self.aliases.get(&key) {
+            Some(canonical) => canonical.clone(),
+            None => key.1,
+        }
+    }
+}
+
+// Return the key of the value before the aliases are applied.
+fn normalize(kind: FieldKind, value: &str) -> String {
+    let value = value.to_lowercase().replace(['.', ','], "");
+    let mut words: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .collect();
+    if matches!(kind, FieldKind::Dev | FieldKind::Publi) {
+        while words.len() > 1 && COMPANY_SUFFIXES.contains(words.last().unwrap()) {
+            words.pop();
+        }
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod test_normalizer {
+    use super::*;
+    #[test]
+    fn canonical_key() {
+        let normalizer = Normalizer::new();
+        for tag in ["Point and Click", "point and click", "point-and-click"] {
+            assert_eq!(normalizer.key(FieldKind::Tag, tag), "point and click");
+        }
+        assert_eq!(
+            normalizer.key(FieldKind::Dev, "Zachtronics, Inc."),
+            normalizer.key(FieldKind::Dev, "Zachtronics")
+        );
+        assert_eq!(normalizer.key(FieldKind::Tag, "Inc."), "inc");
+        assert_ne!(
+            normalizer.key(FieldKind::Engine, "C++"),
+            normalizer.key(FieldKind::Engine, "C")
+        );
+    }
+    #[test]
+    fn aliases() {
+        let normalizer: Normalizer = toml::from_str(
+            "[tags]\n\"point and click\" = [\"pnc\"]\n[devs]\nZachtronics = [\"Zachtronics Industries\"]\n",
+        )
+        .unwrap();
+        assert_eq!(normalizer.key(FieldKind::Tag, "PnC"), "point and click");
+        assert_eq!(
+            normalizer.key(FieldKind::Dev, "Zachtronics Industries Inc."),
+            "zachtronics"
+        );
+        assert_eq!(normalizer.key(FieldKind::Genre, "pnc"), "pnc");
+    }
+}
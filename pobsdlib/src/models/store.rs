@@ -25,11 +25,25 @@ impl fmt::Display for Store {
 }
 
 impl Store {
+    /// Name of the kind of store.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Store::Steam(_) => "Steam",
+            Store::HumbleBundle(_) => "Humble Bundle",
+            Store::Gog(_) => "GOG",
+            Store::Hitchio(_) => "itch.io",
+            Store::Other(_) | Store::NoStore => "unknown",
+        }
+    }
     pub fn new(url: &str) -> Self {
         if url.contains("steampowered") {
             Self::Steam(url.to_string())
         } else if url.contains("humblebundle") {
             Self::HumbleBundle(url.to_string())
+        } else if url.contains("gog.com") {
+            Self::Gog(url.to_string())
+        } else if url.contains("itch.io") {
+            Self::Hitchio(url.to_string())
         } else {
             Self::Other(url.to_string())
         }
@@ -0,0 +1,166 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// An interned string, used for the values of the item
+/// collections (engines, runtimes, genres, tags, years,
+/// developers and publishers). Cloning a symbol shares its
+/// text instead of copying it: the database gives the games
+/// the symbols of its item collections, so that each value is
+/// stored once. A symbol behaves as a string otherwise: it
+/// derefs to str, compares with strings and is serialized as
+/// a string.
+#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    /// Return true if both symbols share the same text.
+    pub fn ptr_eq(&self, other: &Symbol) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Symbol(Arc::from(value))
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Symbol(Arc::from(value))
+    }
+}
+
+impl From<&Symbol> for String {
+    fn from(value: &Symbol) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<Symbol> for str {
+    fn eq(&self, other: &Symbol) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialEq<Symbol> for &str {
+    fn eq(&self, other: &Symbol) -> bool {
+        *self == &*other.0
+    }
+}
+
+impl PartialEq<Symbol> for String {
+    fn eq(&self, other: &Symbol) -> bool {
+        **self == *other.0
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Symbol::from)
+    }
+}
+
+impl JsonSchema for Symbol {
+    fn is_referenceable() -> bool {
+        false
+    }
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod test_symbol {
+    use super::*;
+    use std::collections::HashMap;
+    #[test]
+    fn behaves_as_a_string() {
+        let symbol = Symbol::from("indie");
+        assert_eq!(symbol, "indie");
+        assert_eq!("indie".to_string(), symbol);
+        assert_eq!(format!("{} {:?}", symbol, symbol), "indie \"indie\"");
+        assert_eq!(
+            [symbol.clone(), Symbol::from("free")].join(", "),
+            "indie, free"
+        );
+        let mut map: HashMap<Symbol, usize> = HashMap::new();
+        map.insert(symbol.clone(), 1);
+        assert_eq!(map.get("indie"), Some(&1));
+        assert_eq!(serde_json::to_string(&symbol).unwrap(), "\"indie\"");
+    }
+    #[test]
+    fn sharing() {
+        let symbol = Symbol::from("indie");
+        assert!(symbol.ptr_eq(&symbol.clone()));
+        assert!(!symbol.ptr_eq(&Symbol::from("indie")));
+    }
+}
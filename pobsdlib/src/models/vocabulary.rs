@@ -0,0 +1,296 @@
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::models::{FieldKind, Normalizer};
+use crate::utils::similar_names::levenshtein;
+
+/// A term of the vocabulary.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct Term {
+    /// description of the term.
+    pub description: Option<String>,
+    /// broader term of the vocabulary, if any.
+    pub parent: Option<String>,
+    /// deprecated synonyms, to be replaced by the term.
+    pub deprecated: Vec<String>,
+}
+
+/// Known tags and genres, usually read from a TOML file
+/// such as:
+/// ```toml
+/// [tags.indie]
+/// description = "Made by an independent studio"
+/// deprecated = ["independent"]
+///
+/// [genres."Puzzle Platformer"]
+/// parent = "Platformer"
+///
+/// [genres.Platformer]
+/// ```
+/// A plain list of terms (`tags = ["indie", "free"]`) is
+/// accepted as well. The tags (or genres) are only checked
+/// when some are given.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct Vocabulary {
+    #[serde(deserialize_with = "deserialize_terms")]
+    pub tags: BTreeMap<String, Term>,
+    #[serde(deserialize_with = "deserialize_terms")]
+    pub genres: BTreeMap<String, Term>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Terms {
+    List(Vec<String>),
+    Table(BTreeMap<String, Term>),
+}
+
+fn deserialize_terms<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Term>, D::Error> {
+    Ok(match Terms::deserialize(deserializer)? {
+        Terms::List(terms) => terms
+            .into_iter()
+            .map(|term| (term, Term::default()))
+            .collect(),
+        Terms::Table(terms) => terms,
+    })
+}
+
+/// Problem of a value with regard to the vocabulary.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase", tag = "issue")]
+pub enum TermIssue {
+    /// The value is not a term of the vocabulary, the
+    /// closest term being suggested if any.
+    Unknown { suggestion: Option<String> },
+    /// The value is a deprecated synonym of a term.
+    Deprecated { replacement: String },
+}
+
+/// Display the issue as "unknown" or "deprecated" followed
+/// by the term to use instead, if any.
+impl fmt::Display for TermIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TermIssue::Unknown {
+                suggestion: Some(suggestion),
+            } => write!(f, "unknown (did you mean {}?)", suggestion),
+            TermIssue::Unknown { suggestion: None } => write!(f, "unknown"),
+            TermIssue::Deprecated { replacement } => {
+                write!(f, "deprecated (use {} instead)", replacement)
+            }
+        }
+    }
+}
+
+/// Value of a facet of the database which is not a term of
+/// the vocabulary, with the games using it.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TermReport {
+    /// facet of the value (tags or genres).
+    pub kind: FieldKind,
+    /// the value, as written in the database.
+    pub value: String,
+    /// what is wrong with the value.
+    #[serde(flatten)]
+    pub issue: TermIssue,
+    /// names of the games using the value, in the database order.
+    pub games: Vec<String>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Vocabulary::default()
+    }
+    /// Read the vocabulary from a TOML file.
+    /// Terms whose parent is not a term are refused.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let vocabulary: Vocabulary =
+            toml::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        vocabulary.check_parents()?;
+        Ok(vocabulary)
+    }
+    /// Return the terms whose parent is not a term of the
+    /// same facet, with their parent, sorted by facet and term.
+    pub fn unknown_parents(&self) -> Vec<(FieldKind, &str, &str)> {
+        let mut unknown: Vec<(FieldKind, &str, &str)> = Vec::new();
+        for kind in [FieldKind::Genre, FieldKind::Tag] {
+            let terms = match self.terms(kind) {
+                Some(terms) => terms,
+                None => continue,
+            };
+            for (name, term) in terms {
+                if let Some(parent) = &term.parent {
+                    if !terms.contains_key(parent) {
+                        unknown.push((kind, name, parent));
+                    }
+                }
+            }
+        }
+        unknown
+    }
+    // Fail with an InvalidData error listing the unknown parents.
+    pub(crate) fn check_parents(&self) -> io::Result<()> {
+        let unknown = self.unknown_parents();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        let unknown: Vec<String> = unknown
+            .iter()
+            .map(|(_, name, parent)| format!("{} (parent of {})", parent, name))
+            .collect();
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown parent(s): {}", unknown.join(", ")),
+        ))
+    }
+    /// Return the terms of the given facet (only the tags
+    /// and genres have a vocabulary).
+    pub fn terms(&self, kind: FieldKind) -> Option<&BTreeMap<String, Term>> {
+        match kind {
+            FieldKind::Tag => Some(&self.tags),
+            FieldKind::Genre => Some(&self.genres),
+            _ => None,
+        }
+    }
+    /// Return true if the values of the facet are checked,
+    /// i.e. if the vocabulary has terms for it.
+    pub fn checks(&self, kind: FieldKind) -> bool {
+        self.terms(kind).is_some_and(|terms| !terms.is_empty())
+    }
+    /// Return the term with the given name.
+    pub fn term(&self, kind: FieldKind, name: &str) -> Option<&Term> {
+        self.terms(kind)?.get(name)
+    }
+    /// Return the description of the term with the given name.
+    pub fn description(&self, kind: FieldKind, name: &str) -> Option<&str> {
+        self.term(kind, name)?.description.as_deref()
+    }
+    /// Check the value against the terms of the facet. Nothing
+    /// is reported for a known term or when the facet is not
+    /// checked.
+    pub fn check(&self, kind: FieldKind, value: &str) -> Option<TermIssue> {
+        if !self.checks(kind) {
+            return None;
+        }
+        let terms = self.terms(kind)?;
+        if terms.contains_key(value) {
+            return None;
+        }
+        if let Some((name, _)) = terms
+            .iter()
+            .find(|(_, term)| term.deprecated.iter().any(|synonym| synonym == value))
+        {
+            return Some(TermIssue::Deprecated {
+                replacement: name.clone(),
+            });
+        }
+        Some(TermIssue::Unknown {
+            suggestion: suggestion(kind, terms, value),
+        })
+    }
+}
+
+// Return the term closest to the value: the term (or the term
+// of the deprecated synonym) with the same canonical key (see
+// Normalizer), or else the term with the fewest edits, up to
+// one edit every five characters.
+fn suggestion(kind: FieldKind, terms: &BTreeMap<String, Term>, value: &str) -> Option<String> {
+    let normalizer = Normalizer::new();
+    let key = normalizer.key(kind, value);
+    let spellings = terms.iter().flat_map(|(name, term)| {
+        std::iter::once((name, name.as_str())).chain(
+            term.deprecated
+                .iter()
+                .map(move |synonym| (name, synonym.as_str())),
+        )
+    });
+    if let Some((name, _)) = spellings
+        .clone()
+        .find(|(_, spelling)| normalizer.key(kind, spelling) == key)
+    {
+        return Some(name.clone());
+    }
+    let value = value.to_lowercase();
+    let threshold = (value.chars().count() / 5).max(1);
+    spellings
+        .map(|(name, spelling)| (levenshtein(&spelling.to_lowercase(), &value), name))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, name)| name.clone())
+}
+
+#[cfg(test)]
+mod test_vocabulary {
+    use super::*;
+    fn vocabulary() -> Vocabulary {
+        toml::from_str(
+            "[tags.indie]\ndescription = \"Independent\"\ndeprecated = [\"independent\"]\n[tags.\"point and click\"]\n[genres]\n",
+        )
+        .unwrap()
+    }
+    #[test]
+    fn terms() {
+        let vocabulary = vocabulary();
+        assert_eq!(
+            vocabulary.description(FieldKind::Tag, "indie"),
+            Some("Independent")
+        );
+        assert!(vocabulary.checks(FieldKind::Tag));
+        assert!(!vocabulary.checks(FieldKind::Genre));
+        let list: Vocabulary = toml::from_str("tags = [\"indie\"]\n").unwrap();
+        assert_eq!(list.term(FieldKind::Tag, "indie"), Some(&Term::default()));
+    }
+    #[test]
+    fn parents() {
+        let vocabulary: Vocabulary = toml::from_str(
+            "[genres.\"Puzzle Platformer\"]\nparent = \"Platformer\"\n[genres.Shooter]\nparent = \"Action\"\n[genres.Platformer]\n[tags.indie]\nparent = \"Platformer\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            vocabulary.unknown_parents(),
+            vec![
+                (FieldKind::Genre, "Shooter", "Action"),
+                (FieldKind::Tag, "indie", "Platformer"),
+            ]
+        );
+        assert_eq!(
+            vocabulary.check_parents().unwrap_err().to_string(),
+            "unknown parent(s): Action (parent of Shooter), Platformer (parent of indie)"
+        );
+        assert!(self::vocabulary().check_parents().is_ok());
+    }
+    #[test]
+    fn check() {
+        let vocabulary = vocabulary();
+        assert_eq!(vocabulary.check(FieldKind::Tag, "indie"), None);
+        assert_eq!(vocabulary.check(FieldKind::Genre, "anything"), None);
+        assert_eq!(
+            vocabulary.check(FieldKind::Tag, "independent"),
+            Some(TermIssue::Deprecated {
+                replacement: "indie".to_string()
+            })
+        );
+        for (value, suggestion) in [
+            ("Point-and-Click", Some("point and click")),
+            ("Independent", Some("indie")),
+            ("indi", Some("indie")),
+            ("roguelike", None),
+        ] {
+            assert_eq!(
+                vocabulary.check(FieldKind::Tag, value),
+                Some(TermIssue::Unknown {
+                    suggestion: suggestion.map(String::from)
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use std::cell::Cell;
+
+use crate::record::error::{Error, Result};
+use crate::record::{list_items, RecordLine};
+
+/// Deserialize a sequence of records (e.g. `Vec<T>`) or a
+/// single record from the database format.
+pub fn from_str<'de, T: de::Deserialize<'de>>(data: &'de str) -> Result<T> {
+    T::deserialize(&mut Deserializer::from_str(data))
+}
+
+/// Deserializer of the database format. A record starts
+/// with a line whose key is the key of the first line of
+/// the input (`Game` for the database) and lasts until the
+/// next one. The keys of a record are mapped to the fields
+/// of a struct (or to the keys of a map).
+pub struct Deserializer<'de> {
+    lines: Vec<RecordLine<'de>>,
+    position: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(data: &'de str) -> Self {
+        Deserializer {
+            lines: RecordLine::parse(data),
+            position: 0,
+        }
+    }
+    // Lines of the next record, if any.
+    fn next_record(&mut self) -> Option<&[RecordLine<'de>]> {
+        let start = self.position;
+        let first = self.lines.get(start)?.key;
+        let end = self.lines[start + 1..]
+            .iter()
+            .position(|line| line.key == first)
+            .map_or(self.lines.len(), |offset| start + 1 + offset);
+        self.position = end;
+        Some(&self.lines[start..end])
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Records { de: self })
+    }
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let lines = self.next_record().unwrap_or(&[]);
+        let value = visit_record(lines, visitor)?;
+        match self.lines.get(self.position) {
+            Some(line) => Err(Error::new("expected a single record").at(line.number)),
+            None => Ok(value),
+        }
+    }
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct tuple
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+struct Records<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> SeqAccess<'de> for Records<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        let lines = match self.de.next_record() {
+            Some(lines) => lines,
+            None => return Ok(None),
+        };
+        seed.deserialize(RecordDeserializer { lines }).map(Some)
+    }
+}
+
+// Deserializer of the lines of a single record.
+struct RecordDeserializer<'a, 'de: 'a> {
+    lines: &'a [RecordLine<'de>],
+}
+
+impl<'de, 'a> de::Deserializer<'de> for RecordDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visit_record(self.lines, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+// Visit the lines of a record as a map. Errors are located
+// on the last line read, errors raised by the visitor itself
+// (such as duplicated fields) being raised after reading the
+// offending line.
+fn visit_record<'de, V: Visitor<'de>>(lines: &[RecordLine<'de>], visitor: V) -> Result<V::Value> {
+    let current = Cell::new(lines.first().map_or(1, |line| line.number));
+    visitor
+        .visit_map(Record {
+            lines,
+            value: None,
+            current: &current,
+        })
+        .map_err(|err| err.at(current.get()))
+}
+
+struct Record<'a, 'de: 'a> {
+    lines: &'a [RecordLine<'de>],
+    value: Option<&'a RecordLine<'de>>,
+    current: &'a Cell<usize>,
+}
+
+impl<'de, 'a> MapAccess<'de> for Record<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let (line, rest) = match self.lines.split_first() {
+            Some(split) => split,
+            None => return Ok(None),
+        };
+        self.lines = rest;
+        self.value = Some(line);
+        self.current.set(line.number);
+        seed.deserialize(BorrowedStrDeserializer::<Error>::new(line.key))
+            .map(Some)
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let line = self
+            .value
+            .take()
+            .ok_or_else(|| Error::new("value requested before its key"))?;
+        seed.deserialize(ValueDeserializer {
+            key: line.key,
+            value: line.value,
+            list: true,
+        })
+    }
+}
+
+// Deserializer of a value, an empty value being None.
+// Lists are split according to the key, their items
+// being deserialized as values which are not lists.
+struct ValueDeserializer<'de> {
+    key: &'de str,
+    value: &'de str,
+    list: bool,
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                match self.value.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(Error::new(format!(
+                        "invalid value for {}: {}",
+                        self.key, self.value
+                    ))),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(self.value)
+    }
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.value.is_empty() {
+            visitor.visit_unit()
+        } else {
+            Err(Error::new(format!("{} expects no value", self.key)))
+        }
+    }
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if !self.list {
+            return Err(Error::new(format!("nested list in {}", self.key)));
+        }
+        let key = self.key;
+        visitor.visit_seq(Items {
+            items: list_items(key, self.value)
+                .map(|value| ValueDeserializer {
+                    key,
+                    value,
+                    list: false,
+                })
+                .collect::<Vec<ValueDeserializer>>()
+                .into_iter(),
+        })
+    }
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(BorrowedStrDeserializer::<Error>::new(self.value))
+    }
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+    fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::new(format!("{} cannot hold a map", self.key)))
+    }
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value> {
+        Err(Error::new(format!("{} cannot hold a struct", self.key)))
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf tuple tuple_struct identifier
+    }
+}
+
+struct Items<'de> {
+    items: std::vec::IntoIter<ValueDeserializer<'de>>,
+}
+
+impl<'de> SeqAccess<'de> for Items<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.items.next() {
+            Some(item) => seed.deserialize(item).map(Some),
+            None => Ok(None),
+        }
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
@@ -0,0 +1,50 @@
+use std::error;
+use std::fmt;
+
+/// Error raised while reading or writing records, with the
+/// line of the input when it is known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    pub message: String,
+    pub line: Option<usize>,
+}
+
+/// Result of the record (de)serialization.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub(crate) fn new<T: fmt::Display>(message: T) -> Self {
+        Error {
+            message: message.to_string(),
+            line: None,
+        }
+    }
+    // Set the line of the error, unless already known.
+    pub(crate) fn at(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error::new(message)
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error::new(message)
+    }
+}
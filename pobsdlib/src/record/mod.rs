@@ -0,0 +1,279 @@
+//! Serde data format for the database records.
+//!
+//! Any struct deriving `Serialize` and `Deserialize` whose
+//! fields are named (or renamed) after the keys of the
+//! database can be read from and written to the database
+//! format, following the rules of the database:
+//! - each line holds a key and a value separated by a tab,
+//!   surrounding whitespaces being ignored and empty lines
+//!   being skipped,
+//! - a record starts with the key of the first line of the
+//!   input (`Game` in the database),
+//! - a key without value is read as None,
+//! - lists are separated by spaces for `Store` and by commas
+//!   for other keys (e.g. `Genre` and `Tags`).
+//!
+//! Unknown keys are ignored unless the struct denies them.
+//! [`GameRecord`] describes the records of the database.
+//!
+//! ```
+//! extern crate pobsdlib;
+//! #[macro_use]
+//! extern crate serde_derive;
+//! use pobsdlib::record;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Entry {
+//!     #[serde(rename = "Game")]
+//!     name: String,
+//!     #[serde(rename = "Tags")]
+//!     tags: Option<Vec<String>>,
+//!     #[serde(rename = "Rating")]
+//!     rating: Option<u8>,
+//! }
+//!
+//! # fn main() {
+//! let entries: Vec<Entry> = record::from_str("Game\tShuggy\nTags\tindie, free\nRating\t4\n").unwrap();
+//! assert_eq!(entries[0].tags, Some(vec!["indie".to_string(), "free".to_string()]));
+//! assert_eq!(
+//!     record::to_string(&entries).unwrap(),
+//!     "Game\tShuggy\nTags\tindie, free\nRating\t4\n"
+//! );
+//! # }
+//! ```
+use chrono::NaiveDate;
+
+use crate::models::{Game, Symbol};
+use crate::utils::split_line;
+
+pub use crate::record::de::{from_str, Deserializer};
+pub use crate::record::error::{Error, Result};
+pub use crate::record::ser::{to_string, Serializer};
+
+pub mod de;
+pub mod error;
+pub mod ser;
+
+// A line of the input, with its number.
+struct RecordLine<'a> {
+    number: usize,
+    key: &'a str,
+    value: &'a str,
+}
+
+impl<'a> RecordLine<'a> {
+    fn parse(data: &'a str) -> Vec<RecordLine<'a>> {
+        let mut lines: Vec<RecordLine> = Vec::new();
+        for (index, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = split_line(line);
+            lines.push(RecordLine {
+                number: index + 1,
+                key: key.unwrap_or("").trim(),
+                value: value.unwrap_or("").trim(),
+            });
+        }
+        lines
+    }
+}
+
+/// Return the separator joining the items of a list
+/// for the given key.
+pub fn list_separator(key: &str) -> &'static str {
+    if key == "Store" {
+        " "
+    } else {
+        ", "
+    }
+}
+
+// Split a list according to its key, empty items being skipped.
+fn list_items<'a>(key: &str, value: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+    let items: Box<dyn Iterator<Item = &'a str>> = if key == "Store" {
+        Box::new(value.split_whitespace())
+    } else {
+        Box::new(value.split(','))
+    };
+    Box::new(
+        items
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty()),
+    )
+}
+
+/// (De)serialization of optional dates with the database
+/// rules: dates are written as YYYY-MM-DD and read in the
+/// formats accepted by [`parse_date`](crate::utils::parse_date).
+/// To be used with `#[serde(default, with = "pobsdlib::record::date")]`.
+pub mod date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::utils::parse_date;
+
+    pub fn serialize<S: Serializer>(
+        date: &Option<NaiveDate>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match date {
+            Some(date) => serializer.collect_str(&date.format("%F")),
+            None => serializer.serialize_none(),
+        }
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDate>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(date) => parse_date(&date)
+                .map(Some)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid date: {}", date))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// A game as written in the database, each field being
+/// named after its key. Unlike [`Game`], it holds neither
+/// id nor uuid.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct GameRecord {
+    #[serde(rename = "Game")]
+    pub name: String,
+    #[serde(rename = "Cover")]
+    pub cover: Option<String>,
+    #[serde(rename = "Engine")]
+    pub engine: Option<Symbol>,
+    #[serde(rename = "Setup")]
+    pub setup: Option<String>,
+    #[serde(rename = "Runtime")]
+    pub runtime: Option<Symbol>,
+    #[serde(rename = "Store")]
+    pub stores: Option<Vec<String>>,
+    #[serde(rename = "Hints")]
+    pub hints: Option<String>,
+    #[serde(rename = "Genre")]
+    pub genres: Option<Vec<Symbol>>,
+    #[serde(rename = "Tags")]
+    pub tags: Option<Vec<Symbol>>,
+    #[serde(rename = "Year")]
+    pub year: Option<Symbol>,
+    #[serde(rename = "Dev")]
+    pub dev: Option<Symbol>,
+    #[serde(rename = "Pub")]
+    pub publi: Option<Symbol>,
+    #[serde(rename = "Version")]
+    pub version: Option<String>,
+    #[serde(rename = "Status")]
+    pub status: Option<String>,
+    #[serde(rename = "Added", default, with = "date")]
+    pub added: Option<NaiveDate>,
+    #[serde(rename = "Updated", default, with = "date")]
+    pub updated: Option<NaiveDate>,
+}
+
+impl From<Game> for GameRecord {
+    fn from(game: Game) -> Self {
+        GameRecord {
+            name: game.name,
+            cover: game.cover,
+            engine: game.engine,
+            setup: game.setup,
+            runtime: game.runtime,
+            stores: game.stores,
+            hints: game.hints,
+            genres: game.genres,
+            tags: game.tags,
+            year: game.year,
+            dev: game.dev,
+            publi: game.publi,
+            version: game.version,
+            status: game.status,
+            added: game.added,
+            updated: game.updated,
+        }
+    }
+}
+
+impl GameRecord {
+    /// Return the game, with the given id and the
+    /// uuid computed from its name.
+    pub fn into_game(self, id: usize) -> Game {
+        Game {
+            id,
+            uuid: Game::compute_uuid(&self.name),
+            name: self.name,
+            cover: self.cover,
+            engine: self.engine,
+            setup: self.setup,
+            runtime: self.runtime,
+            stores: self.stores,
+            hints: self.hints,
+            genres: self.genres,
+            tags: self.tags,
+            year: self.year,
+            dev: self.dev,
+            publi: self.publi,
+            version: self.version,
+            status: self.status,
+            added: self.added,
+            updated: self.updated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_record {
+    use super::*;
+    use std::collections::BTreeMap;
+    fn record() -> GameRecord {
+        GameRecord {
+            name: "Shuggy".to_string(),
+            stores: Some(vec![
+                "https://a.example.org".to_string(),
+                "https://b.example.org".to_string(),
+            ]),
+            genres: Some(vec!["Puzzle Platformer".into()]),
+            tags: Some(vec!["indie".into(), "free".into()]),
+            added: NaiveDate::from_ymd_opt(2020, 1, 2),
+            ..Default::default()
+        }
+    }
+    const RECORD: &str = "Game\tShuggy\nCover\nEngine\nSetup\nRuntime\nStore\thttps://a.example.org https://b.example.org\nHints\nGenre\tPuzzle Platformer\nTags\tindie, free\nYear\nDev\nPub\nVersion\nStatus\nAdded\t2020-01-02\nUpdated\n";
+    #[test]
+    fn game_record_round_trip() {
+        assert_eq!(to_string(&[record(), record()]).unwrap(), RECORD.repeat(2));
+        assert_eq!(
+            from_str::<Vec<GameRecord>>(&RECORD.repeat(2)).unwrap(),
+            vec![record(), record()]
+        );
+        assert_eq!(from_str::<GameRecord>(RECORD).unwrap(), record());
+    }
+    #[test]
+    fn database_rules() {
+        let data = "\nGame\t Shuggy \nTags\tindie,free,\nStore\thttps://a.example.org  https://b.example.org\nGenre\tPuzzle Platformer\nAdded\t2020/01/02\nNotes\tignored\n";
+        assert_eq!(from_str::<GameRecord>(data).unwrap(), record());
+    }
+    #[test]
+    fn errors() {
+        let err = from_str::<Vec<GameRecord>>("Game\ta\nAdded\tyesterday\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        let err = from_str::<Vec<GameRecord>>("Game\ta\nGame\tb\nTags\tx\nTags\ty\n").unwrap_err();
+        assert_eq!(err.line, Some(4));
+        assert!(from_str::<GameRecord>("Game\ta\nGame\tb\n").is_err());
+        assert!(to_string(&GameRecord {
+            name: "a\tb".to_string(),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(to_string(&1).is_err());
+    }
+    #[test]
+    fn maps() {
+        let records: Vec<BTreeMap<String, String>> =
+            from_str("Game\ta\nEngine\tFNA\nGame\tb\n").unwrap();
+        assert_eq!(records[0]["Engine"], "FNA");
+        assert_eq!(records[1].len(), 1);
+    }
+}
@@ -0,0 +1,406 @@
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::record::error::{Error, Result};
+use crate::record::list_separator;
+
+/// Serialize a sequence of records (e.g. `&[T]`) or a single
+/// record in the database format. Each field is written on
+/// its own line, fields without value (None) being written
+/// as their key alone.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    let mut output = String::new();
+    value.serialize(&mut Serializer {
+        output: &mut output,
+    })?;
+    Ok(output)
+}
+
+/// Serializer of the database format.
+pub struct Serializer<'a> {
+    output: &'a mut String,
+}
+
+fn unsupported<T>(what: &str) -> Result<T> {
+    Err(Error::new(format!(
+        "expected a sequence of records or a record, got {}",
+        what
+    )))
+}
+
+macro_rules! unsupported {
+    ($($method:ident($($arg:ty),*) -> $what:expr,)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<()> {
+                unsupported($what)
+            }
+        )*
+    };
+}
+
+impl<'a, 'b> ser::Serializer for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = RecordSerializer<'b>;
+    type SerializeStruct = RecordSerializer<'b>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    unsupported! {
+        serialize_bool(bool) -> "a boolean",
+        serialize_i8(i8) -> "a number",
+        serialize_i16(i16) -> "a number",
+        serialize_i32(i32) -> "a number",
+        serialize_i64(i64) -> "a number",
+        serialize_u8(u8) -> "a number",
+        serialize_u16(u16) -> "a number",
+        serialize_u32(u32) -> "a number",
+        serialize_u64(u64) -> "a number",
+        serialize_f32(f32) -> "a number",
+        serialize_f64(f64) -> "a number",
+        serialize_char(char) -> "a character",
+        serialize_str(&str) -> "a string",
+        serialize_bytes(&[u8]) -> "bytes",
+        serialize_none() -> "none",
+        serialize_unit() -> "unit",
+        serialize_unit_struct(&'static str) -> "unit",
+        serialize_unit_variant(&'static str, u32, &'static str) -> "an enum",
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<()> {
+        unsupported("an enum")
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self> {
+        Ok(self)
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        unsupported("a tuple struct")
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        unsupported("an enum")
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<RecordSerializer<'b>> {
+        Ok(RecordSerializer {
+            output: self.output,
+            key: None,
+        })
+    }
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<RecordSerializer<'b>> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        unsupported("an enum")
+    }
+}
+
+impl<'a, 'b> ser::SerializeSeq for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut Serializer {
+            output: self.output,
+        })
+    }
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeTuple for &'b mut Serializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Serializer of the fields of a record.
+pub struct RecordSerializer<'a> {
+    output: &'a mut String,
+    key: Option<String>,
+}
+
+impl<'a> RecordSerializer<'a> {
+    fn write_line(&mut self, key: &str, value: Option<String>) -> Result<()> {
+        if key.is_empty() || key.contains(['\t', '\n', '\r']) {
+            return Err(Error::new(format!("invalid key: {:?}", key)));
+        }
+        self.output.push_str(key);
+        if let Some(value) = value.filter(|value| !value.is_empty()) {
+            if value.contains(['\t', '\n', '\r']) {
+                return Err(Error::new(format!(
+                    "the value of {} contains a tab or a new line",
+                    key
+                )));
+            }
+            self.output.push('\t');
+            self.output.push_str(&value);
+        }
+        self.output.push('\n');
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStruct for RecordSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let value = value.serialize(ValueSerializer { key, list: true })?;
+        self.write_line(key, value)
+    }
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeMap for RecordSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let key = key
+            .serialize(ValueSerializer {
+                key: "key",
+                list: false,
+            })?
+            .unwrap_or_default();
+        self.key = Some(key);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("value given before its key"))?;
+        let value = value.serialize(ValueSerializer {
+            key: &key,
+            list: true,
+        })?;
+        self.write_line(&key, value)
+    }
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// Serializer of a value into its text, None meaning that
+// the field has no value. Lists are joined according to
+// the key.
+struct ValueSerializer<'k> {
+    key: &'k str,
+    list: bool,
+}
+
+macro_rules! serialize_display {
+    ($($method:ident($type:ty),)*) => {
+        $(
+            fn $method(self, value: $type) -> Result<Option<String>> {
+                Ok(Some(value.to_string()))
+            }
+        )*
+    };
+}
+
+impl<'k> ValueSerializer<'k> {
+    fn unsupported<T>(&self, what: &str) -> Result<T> {
+        Err(Error::new(format!("{} cannot hold {}", self.key, what)))
+    }
+}
+
+impl<'k> ser::Serializer for ValueSerializer<'k> {
+    type Ok = Option<String>;
+    type Error = Error;
+    type SerializeSeq = ListSerializer<'k>;
+    type SerializeTuple = ListSerializer<'k>;
+    type SerializeTupleStruct = Impossible<Option<String>, Error>;
+    type SerializeTupleVariant = Impossible<Option<String>, Error>;
+    type SerializeMap = Impossible<Option<String>, Error>;
+    type SerializeStruct = Impossible<Option<String>, Error>;
+    type SerializeStructVariant = Impossible<Option<String>, Error>;
+
+    serialize_display! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+    fn serialize_bytes(self, _value: &[u8]) -> Result<Option<String>> {
+        self.unsupported("bytes")
+    }
+    fn serialize_none(self) -> Result<Option<String>> {
+        Ok(None)
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Option<String>> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Option<String>> {
+        Ok(None)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Option<String>> {
+        Ok(None)
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Option<String>> {
+        Ok(Some(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Option<String>> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Option<String>> {
+        self.unsupported("an enum with data")
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<ListSerializer<'k>> {
+        if !self.list {
+            return self.unsupported("nested lists");
+        }
+        Ok(ListSerializer {
+            key: self.key,
+            items: Vec::new(),
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer<'k>> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.unsupported("a tuple struct")
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.unsupported("an enum with data")
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.unsupported("a map")
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        self.unsupported("a struct")
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.unsupported("an enum with data")
+    }
+}
+
+struct ListSerializer<'k> {
+    key: &'k str,
+    items: Vec<String>,
+}
+
+impl<'k> ser::SerializeSeq for ListSerializer<'k> {
+    type Ok = Option<String>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let item = value.serialize(ValueSerializer {
+            key: self.key,
+            list: false,
+        })?;
+        if let Some(item) = item.filter(|item| !item.is_empty()) {
+            self.items.push(item);
+        }
+        Ok(())
+    }
+    fn end(self) -> Result<Option<String>> {
+        if self.items.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(self.items.join(list_separator(self.key))))
+        }
+    }
+}
+
+impl<'k> ser::SerializeTuple for ListSerializer<'k> {
+    type Ok = Option<String>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Option<String>> {
+        ser::SerializeSeq::end(self)
+    }
+}
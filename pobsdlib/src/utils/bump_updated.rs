@@ -0,0 +1,135 @@
+use crate::models::ChangeKind;
+use crate::utils::database_builder::DataBaseBuilder;
+use crate::utils::diff_databases;
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+/// The BumpResult struct represents the outcome of
+/// bump_updated.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct BumpResult {
+    /// database with the Updated lines set.
+    pub content: String,
+    /// names of the games whose Updated line was set.
+    pub games: Vec<String>,
+}
+
+// Names of the games added or modified since base, without
+// their Updated field being changed.
+fn games_to_bump(base: &str, data: &str) -> Vec<String> {
+    let base = DataBaseBuilder::verbatim().build_from_string(base.to_string());
+    let new = DataBaseBuilder::verbatim().build_from_string(data.to_string());
+    let mut games: Vec<String> = Vec::new();
+    for change in diff_databases(&base, &new) {
+        let bump = match &change.kind {
+            ChangeKind::Added => new
+                .games
+                .get(&change.uuid)
+                .map(|game| game.updated.is_none())
+                .unwrap_or(false),
+            ChangeKind::Modified(fields) => !fields.iter().any(|field| field.field == "Updated"),
+            ChangeKind::Removed => false,
+        };
+        if bump {
+            games.push(change.name);
+        }
+    }
+    games
+}
+
+/// Set the Updated line of the games added or modified since
+/// the base version of the database to the given date.
+///
+/// Games whose Updated line was already changed are left as
+/// they are. A missing Updated line is added at the end of the
+/// game. All other lines, including their line endings, are
+/// kept byte for byte.
+pub fn bump_updated(base: &str, data: &str, date: NaiveDate) -> BumpResult {
+    let games = games_to_bump(base, data);
+    let names: HashSet<&str> = games.iter().map(|name| name.as_str()).collect();
+    let updated = format!("Updated\t{}", date.format("%F"));
+    let mut content = String::with_capacity(data.len());
+    // the current game is to be bumped, its Updated
+    // line not being found yet
+    let mut pending = false;
+    let mut ending = "\n";
+    for line in data.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        ending = &line[text.len()..];
+        let key = text.split('\t').next().unwrap_or("");
+        if key == "Game" {
+            if pending {
+                content.push_str(&updated);
+                content.push_str(ending);
+            }
+            let name = text.split('\t').nth(1).unwrap_or("").trim();
+            pending = names.contains(name);
+        } else if key == "Updated" && pending {
+            content.push_str(&updated);
+            content.push_str(ending);
+            pending = false;
+            continue;
+        }
+        content.push_str(line);
+    }
+    if pending {
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&updated);
+        content.push_str(if ending.is_empty() { "\n" } else { ending });
+    }
+    BumpResult { content, games }
+}
+
+#[cfg(test)]
+mod test_bump_updated {
+    use super::*;
+    fn game(name: &str, engine: &str, updated: &str) -> String {
+        format!(
+            "Game\t{}\nEngine\t{}\nAdded\t2020-01-01\nUpdated\t{}\n",
+            name, engine, updated
+        )
+    }
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+    }
+    #[test]
+    fn only_modified_games() {
+        let base = format!(
+            "{}{}{}",
+            game("a", "XNA", "2020-01-01"),
+            game("b", "XNA", "2020-01-01"),
+            game("c", "XNA", "2020-01-01")
+        );
+        // a is modified, b is left untouched, c is modified
+        // with its Updated line
+        let data = format!(
+            "{}{}{}",
+            game("a", "FNA", "2020-01-01"),
+            game("b", "XNA", "2020-01-01"),
+            game("c", "FNA", "2021-01-01")
+        );
+        let result = bump_updated(&base, &data, today());
+        assert_eq!(result.games, vec!["a".to_string()]);
+        assert_eq!(
+            result.content,
+            format!(
+                "{}{}{}",
+                game("a", "FNA", "2024-05-01"),
+                game("b", "XNA", "2020-01-01"),
+                game("c", "FNA", "2021-01-01")
+            )
+        );
+    }
+    #[test]
+    fn bytes_are_kept() {
+        let base = "Game\ta\r\nEngine\tXNA \r\nGame\tb\r\nEngine\tXNA\r\n";
+        let data = "Game\ta\r\nEngine\tXNA \r\nGame\tb\r\nEngine\tFNA\r\n";
+        let result = bump_updated(base, data, today());
+        assert_eq!(
+            result.content,
+            "Game\ta\r\nEngine\tXNA \r\nGame\tb\r\nEngine\tFNA\r\nUpdated\t2024-05-01\r\n"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use crate::models::{Game, Item, Symbol};
+use crate::utils::parse_date;
+use std::io;
+
+/// Columns available for the games, named as in the
+/// JSON representation of the games.
+pub const COLUMNS: [&str; 17] = [
+    "id", "name", "cover", "engine", "setup", "runtime", "stores", "hints", "genres", "tags",
+    "year", "dev", "pub", "version", "status", "added", "updated",
+];
+
+/// Separator joining multiple values (stores, genres and
+/// tags) by default.
+pub const LIST_SEPARATOR: &str = ", ";
+
+/// Settings of the CSV export and import.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    delimiter: u8,
+    columns: Vec<String>,
+    list_separator: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions::csv()
+    }
+}
+
+impl CsvOptions {
+    /// Comma separated values with all columns, multiple
+    /// values being joined by ", ".
+    pub fn csv() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            columns: COLUMNS.iter().map(|column| column.to_string()).collect(),
+            list_separator: LIST_SEPARATOR.to_string(),
+        }
+    }
+    /// Tab separated values with all columns, multiple
+    /// values being joined by ", ".
+    pub fn tsv() -> Self {
+        CsvOptions {
+            delimiter: b'\t',
+            ..CsvOptions::csv()
+        }
+    }
+    /// Set the columns of the export. Unknown columns
+    /// are rejected.
+    pub fn set_columns(&mut self, columns: &[&str]) -> io::Result<()> {
+        if let Some(column) = columns.iter().find(|column| !COLUMNS.contains(column)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown column: {}", column),
+            ));
+        }
+        self.columns = columns.iter().map(|column| column.to_string()).collect();
+        Ok(())
+    }
+    /// Set the separator used to join (and split on import)
+    /// stores, genres and tags.
+    pub fn set_list_separator(&mut self, separator: &str) {
+        self.list_separator = separator.to_string();
+    }
+}
+
+/// Return the value of the given column for the game, or
+/// None if the game has no value or the column is unknown.
+pub fn game_column(game: &Game, column: &str, list_separator: &str) -> Option<String> {
+    match column {
+        "id" => Some(game.id.to_string()),
+        "name" => Some(game.name.clone()),
+        "cover" => game.cover.clone(),
+        "engine" => game.engine.as_deref().map(String::from),
+        "setup" => game.setup.clone(),
+        "runtime" => game.runtime.as_deref().map(String::from),
+        "stores" => game
+            .stores
+            .as_ref()
+            .map(|stores| stores.join(list_separator)),
+        "hints" => game.hints.clone(),
+        "genres" => game
+            .genres
+            .as_ref()
+            .map(|genres| genres.join(list_separator)),
+        "tags" => game.tags.as_ref().map(|tags| tags.join(list_separator)),
+        "year" => game.year.as_deref().map(String::from),
+        "dev" => game.dev.as_deref().map(String::from),
+        "pub" => game.publi.as_deref().map(String::from),
+        "version" => game.version.clone(),
+        "status" => game.status.clone(),
+        "added" => game.added.map(|date| date.format("%F").to_string()),
+        "updated" => game.updated.map(|date| date.format("%F").to_string()),
+        _ => None,
+    }
+}
+
+fn csv_error(err: csv::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Write the games, one per row, preceded by a header row
+/// with the name of the columns.
+pub fn write_games<W: io::Write>(
+    games: &[Game],
+    options: &CsvOptions,
+    writer: W,
+) -> io::Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+    writer.write_record(&options.columns).map_err(csv_error)?;
+    for game in games {
+        writer
+            .write_record(options.columns.iter().map(|column| {
+                game_column(game, column, &options.list_separator).unwrap_or_default()
+            }))
+            .map_err(csv_error)?;
+    }
+    writer.flush()
+}
+
+/// Write the rows preceded by a header row. Only the
+/// delimiter of the options is used.
+pub fn write_rows<W: io::Write>(
+    header: &[&str],
+    rows: &[Vec<String>],
+    options: &CsvOptions,
+    writer: W,
+) -> io::Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+    writer.write_record(header).map_err(csv_error)?;
+    for row in rows {
+        writer.write_record(row).map_err(csv_error)?;
+    }
+    writer.flush()
+}
+
+/// Write the items (engines, tags...) with the number of
+/// games associated to each of them.
+pub fn write_facets<W: io::Write>(
+    items: &[&Item],
+    options: &CsvOptions,
+    writer: W,
+) -> io::Result<()> {
+    let rows: Vec<Vec<String>> = items
+        .iter()
+        .map(|item| vec![item.name.to_string(), item.count().to_string()])
+        .collect();
+    write_rows(&["name", "games"], &rows, options, writer)
+}
+
+fn set_column(game: &mut Game, column: &str, value: &str, list_separator: &str) -> io::Result<()> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    let text = Some(value.to_string());
+    let symbol = Some(Symbol::from(value));
+    // the separator is trimmed so that "a,b" is read as
+    // "a, b", unless it is only made of whitespace (e.g. " ")
+    let separator = match list_separator.trim() {
+        "" => list_separator,
+        trimmed => trimmed,
+    };
+    let list = || {
+        Some(
+            value
+                .split(separator)
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect(),
+        )
+    };
+    let symbols = || list().map(|items: Vec<String>| items.into_iter().map(Symbol::from).collect());
+    let date = || {
+        parse_date(value).map(Some).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid {} date: {}", column, value),
+            )
+        })
+    };
+    match column {
+        "id" => {
+            game.id = value.parse().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("invalid id: {}", value))
+            })?
+        }
+        "name" => game.name = value.to_string(),
+        "cover" => game.cover = text,
+        "engine" => game.engine = symbol,
+        "setup" => game.setup = text,
+        "runtime" => game.runtime = symbol,
+        "stores" => game.stores = list(),
+        "hints" => game.hints = text,
+        "genres" => game.genres = symbols(),
+        "tags" => game.tags = symbols(),
+        "year" => game.year = symbol,
+        "dev" => game.dev = symbol,
+        "pub" => game.publi = symbol,
+        "version" => game.version = text,
+        "status" => game.status = text,
+        "added" => game.added = date()?,
+        "updated" => game.updated = date()?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Read games written by write_games. The columns are taken
+/// from the header row, unknown columns being ignored, and
+/// only the delimiter and the list separator of the options
+/// are used. Without id column, the games are numbered in
+/// the order of the rows.
+pub fn read_games<R: io::Read>(reader: R, options: &CsvOptions) -> io::Result<Vec<Game>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .from_reader(reader);
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(|header| header.trim().to_lowercase())
+        .collect();
+    if !headers.iter().any(|header| header == "name") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing name column",
+        ));
+    }
+    let mut games: Vec<Game> = Vec::new();
+    for (position, record) in reader.records().enumerate() {
+        let record = record.map_err(csv_error)?;
+        let mut game = Game {
+            id: position + 1,
+            ..Default::default()
+        };
+        for (column, value) in headers.iter().zip(record.iter()) {
+            set_column(&mut game, column, value, &options.list_separator)?;
+        }
+        game.uuid = Game::compute_uuid(&game.name);
+        games.push(game);
+    }
+    Ok(games)
+}
+
+#[cfg(test)]
+mod test_csv_io {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::collections::BTreeSet;
+    fn game() -> Game {
+        Game {
+            id: 1,
+            uuid: Game::compute_uuid("Shuggy, \"the\" game"),
+            name: "Shuggy, \"the\" game".to_string(),
+            engine: Some("FNA".into()),
+            stores: Some(vec![
+                "https://a.example.org".to_string(),
+                "https://b.example.org".to_string(),
+            ]),
+            tags: Some(vec!["indie".into(), "free".into()]),
+            added: NaiveDate::from_ymd_opt(2020, 1, 1),
+            ..Default::default()
+        }
+    }
+    #[test]
+    fn round_trip() {
+        for options in [CsvOptions::csv(), CsvOptions::tsv()] {
+            let mut data: Vec<u8> = Vec::new();
+            write_games(&[game()], &options, &mut data).unwrap();
+            assert_eq!(read_games(data.as_slice(), &options).unwrap(), vec![game()]);
+        }
+    }
+    #[test]
+    fn columns_and_separator() {
+        let mut options = CsvOptions::csv();
+        options.set_columns(&["name", "tags"]).unwrap();
+        options.set_list_separator("|");
+        let mut data: Vec<u8> = Vec::new();
+        write_games(&[game()], &options, &mut data).unwrap();
+        assert_eq!(
+            String::from_utf8(data.clone()).unwrap(),
+            "name,tags\n\"Shuggy, \"\"the\"\" game\",indie|free\n"
+        );
+        let games = read_games(data.as_slice(), &options).unwrap();
+        assert_eq!(games[0].tags, game().tags);
+        assert_eq!(games[0].engine, None);
+        assert!(options.set_columns(&["name", "colour"]).is_err());
+    }
+    #[test]
+    fn whitespace_separator() {
+        let mut options = CsvOptions::csv();
+        options.set_list_separator(" ");
+        let games = read_games(
+            "name,stores\nA,https://a.org https://b.org\n".as_bytes(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            games[0].stores,
+            Some(vec![
+                "https://a.org".to_string(),
+                "https://b.org".to_string()
+            ])
+        );
+    }
+    #[test]
+    fn facets() {
+        let item = Item {
+            name: "FNA".into(),
+            games: BTreeSet::from([1, 2]),
+        };
+        let mut data: Vec<u8> = Vec::new();
+        write_facets(&[&item], &CsvOptions::tsv(), &mut data).unwrap();
+        assert_eq!(String::from_utf8(data).unwrap(), "name\tgames\nFNA\t2\n");
+    }
+}
@@ -1,6 +1,17 @@
-use crate::collections::DataBase;
-use crate::models::Field;
+use crate::collections::{source_hash, DataBase};
+use crate::models::{Field, Game};
 use crate::utils::{game_dispatch, read_lines};
+#[cfg(feature = "async")]
+use std::future::{self, Future};
+use std::io::{self, BufRead};
+use std::mem;
+use std::path::Path;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::task::Poll;
+#[cfg(feature = "async")]
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
 
 #[derive(Default)]
 pub struct Cursor {
@@ -20,6 +31,7 @@ impl Cursor {
 pub struct DataBaseBuilder {
     expand_cover: bool,
     steam_cover: bool,
+    fill_updated: bool,
 }
 
 impl DataBaseBuilder {
@@ -27,6 +39,17 @@ impl DataBaseBuilder {
         Self {
             expand_cover,
             steam_cover,
+            fill_updated: true,
+        }
+    }
+    /// Return a builder keeping the data as written in
+    /// the database: covers are neither expanded nor taken
+    /// from Steam and missing updated dates are not filled.
+    pub fn verbatim() -> Self {
+        Self {
+            expand_cover: false,
+            steam_cover: false,
+            fill_updated: false,
         }
     }
     pub fn set_expand_cover(&mut self, setting: bool) {
@@ -35,6 +58,11 @@ impl DataBaseBuilder {
     pub fn set_steam_cover(&mut self, setting: bool) {
         self.steam_cover = setting;
     }
+    /// When set (the default), a game without updated date
+    /// is considered updated when it was added.
+    pub fn set_fill_updated(&mut self, setting: bool) {
+        self.fill_updated = setting;
+    }
     pub fn build_from_string(self, data: String) -> DataBase {
         let mut cursor = Cursor::new();
         let mut database = DataBase::default();
@@ -43,23 +71,180 @@ impl DataBaseBuilder {
         }
         database
     }
+    /// Build the database from the data, reusing the cache
+    /// stored in the given file when it was built from the
+    /// same data with the same settings. The cache is written
+    /// otherwise, failures to write it being only reported.
+    pub fn build_from_string_cached(self, data: String, cache: &Path) -> DataBase {
+        let mut source = source_hash(data.as_bytes()).to_le_bytes().to_vec();
+        source.extend_from_slice(&[
+            self.expand_cover as u8,
+            self.steam_cover as u8,
+            self.fill_updated as u8,
+        ]);
+        let source = source_hash(&source);
+        if let Ok(database) = DataBase::load_cache(cache, source) {
+            return database;
+        }
+        let database = self.build_from_string(data);
+        if let Err(err) = database.save_cache(cache, source) {
+            eprintln!("Could not write the cache {}: {}", cache.display(), err);
+        }
+        database
+    }
     pub fn build_from_file(self, filename: &str) -> DataBase {
         let mut cursor = Cursor::new();
         let mut database = DataBase::default();
         if let Ok(lines) = read_lines(filename) {
-            for line in lines.flatten() {
+            for line in lines.map_while(Result::ok) {
                 self.dispatch_line(&mut database, &mut cursor, &line);
             }
         }
         database
     }
+    /// Build the database from the lines of the reader,
+    /// stopping at the first read error.
+    pub fn build_from_reader<R: BufRead>(self, reader: R) -> io::Result<DataBase> {
+        let mut cursor = Cursor::new();
+        let mut database = DataBase::default();
+        for line in reader.lines() {
+            self.dispatch_line(&mut database, &mut cursor, &line?);
+        }
+        Ok(database)
+    }
+    /// Build the database from the lines of the asynchronous
+    /// reader (e.g. the body of an HTTP response), each line
+    /// being parsed as soon as it is read.
+    #[cfg(feature = "async")]
+    pub fn build_from_async_reader<R: AsyncBufRead + Unpin>(
+        self,
+        reader: R,
+    ) -> impl Future<Output = io::Result<DataBase>> {
+        let mut cursor = Cursor::new();
+        let mut database = DataBase::default();
+        let mut lines = reader.lines();
+        // the crate being on edition 2015, the future
+        // is written by hand instead of with async/await
+        future::poll_fn(move |context| loop {
+            match Pin::new(&mut lines).poll_next_line(context) {
+                Poll::Ready(Ok(Some(line))) => {
+                    self.dispatch_line(&mut database, &mut cursor, &line)
+                }
+                Poll::Ready(Ok(None)) => return Poll::Ready(Ok(mem::take(&mut database))),
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        })
+    }
+    /// Return an iterator over the games read from the reader,
+    /// each game being yielded once all its lines are read.
+    /// The games are built as in the database (same ids and
+    /// settings) but no index is built.
+    pub fn games_from_reader<R: BufRead>(self, reader: R) -> GameReader<R> {
+        GameReader {
+            builder: self,
+            lines: reader.lines(),
+            database: DataBase::default(),
+            cursor: Cursor::new(),
+        }
+    }
     fn dispatch_line(&self, database: &mut DataBase, cursor: &mut Cursor, line: &str) {
+        // empty lines carry no information
+        if line.trim().is_empty() {
+            return;
+        }
         game_dispatch(
-            Field::from(&line),
+            Field::from(line),
             database,
             self.expand_cover,
             self.steam_cover,
+            self.fill_updated,
             cursor,
         );
     }
 }
+
+/// Iterator over the games of a reader, see
+/// DataBaseBuilder::games_from_reader.
+pub struct GameReader<R> {
+    builder: DataBaseBuilder,
+    lines: io::Lines<R>,
+    // holds the game being read only
+    database: DataBase,
+    cursor: Cursor,
+}
+
+impl<R> GameReader<R> {
+    fn take_game(&mut self) -> Option<Game> {
+        mem::take(&mut self.database)
+            .games
+            .remove(&self.cursor.uuid)
+    }
+}
+
+impl<R: BufRead> Iterator for GameReader<R> {
+    type Item = io::Result<Game>;
+
+    fn next(&mut self) -> Option<io::Result<Game>> {
+        loop {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(err)) => return Some(Err(err)),
+                None => return self.take_game().map(Ok),
+            };
+            // a new game completes the one being read
+            let done = match Field::from(line.as_str()) {
+                Field::Game(Some(_)) => self.take_game(),
+                _ => None,
+            };
+            self.builder
+                .dispatch_line(&mut self.database, &mut self.cursor, &line);
+            if let Some(game) = done {
+                return Some(Ok(game));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_database_builder {
+    use super::*;
+    const DATA: &str =
+        "Game\tShuggy\nEngine\tFNA\nTags\tindie\n\nGame\tKathy Rain\nAdded\t2020-01-01\nUpdated\n";
+    #[test]
+    fn reader() {
+        let database = DataBaseBuilder::verbatim()
+            .build_from_reader(DATA.as_bytes())
+            .unwrap();
+        assert_eq!(
+            database,
+            DataBaseBuilder::verbatim().build_from_string(DATA.to_string())
+        );
+    }
+    #[test]
+    fn game_reader() {
+        let games: Vec<Game> = DataBaseBuilder::new(false, false)
+            .games_from_reader(DATA.as_bytes())
+            .collect::<io::Result<Vec<Game>>>()
+            .unwrap();
+        let database = DataBaseBuilder::new(false, false).build_from_string(DATA.to_string());
+        assert_eq!(games, database.get_all_games().items);
+        assert_eq!(games[1].updated, games[1].added);
+        assert!(DataBaseBuilder::verbatim()
+            .games_from_reader("".as_bytes())
+            .next()
+            .is_none());
+    }
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_reader() {
+        let database = futures::executor::block_on(
+            DataBaseBuilder::verbatim().build_from_async_reader(DATA.as_bytes()),
+        )
+        .unwrap();
+        assert_eq!(
+            database,
+            DataBaseBuilder::verbatim().build_from_string(DATA.to_string())
+        );
+    }
+}
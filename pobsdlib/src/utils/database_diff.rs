@@ -0,0 +1,74 @@
+use crate::collections::DataBase;
+use crate::models::{ChangeKind, FieldChange, GameChange};
+
+/// Return the changes between two versions of the database.
+/// Added and modified games come first, in the order of the
+/// new database, followed by removed games in the order of
+/// the old database.
+pub fn diff_databases(old: &DataBase, new: &DataBase) -> Vec<GameChange> {
+    let mut changes: Vec<GameChange> = Vec::new();
+    for game in new.get_all_games().items {
+        match old.games.get(&game.uuid) {
+            Some(old_game) => {
+                let fields = FieldChange::between(old_game, &game);
+                if !fields.is_empty() {
+                    changes.push(GameChange {
+                        uuid: game.uuid,
+                        name: game.name,
+                        kind: ChangeKind::Modified(fields),
+                    });
+                }
+            }
+            None => changes.push(GameChange {
+                uuid: game.uuid,
+                name: game.name,
+                kind: ChangeKind::Added,
+            }),
+        }
+    }
+    for game in old.get_all_games().items {
+        if !new.games.contains_key(&game.uuid) {
+            changes.push(GameChange {
+                uuid: game.uuid,
+                name: game.name,
+                kind: ChangeKind::Removed,
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test_diff_databases {
+    use super::*;
+    use crate::DataBaseBuilder;
+    #[test]
+    fn diff_identical() {
+        let data = "Game\tfirst\nEngine\tXNA\nGame\tsecond".to_string();
+        let old = DataBaseBuilder::new(false, false).build_from_string(data.clone());
+        let new = DataBaseBuilder::new(false, false).build_from_string(data);
+        assert!(diff_databases(&old, &new).is_empty());
+    }
+    #[test]
+    fn diff_changes() {
+        let old = "Game\tfirst\nEngine\tXNA\nGame\tsecond".to_string();
+        let new = "Game\tfirst\nEngine\tFNA\nGame\tthird".to_string();
+        let old = DataBaseBuilder::new(false, false).build_from_string(old);
+        let new = DataBaseBuilder::new(false, false).build_from_string(new);
+        let changes = diff_databases(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].name, "first");
+        assert_eq!(
+            changes[0].kind,
+            ChangeKind::Modified(vec![FieldChange {
+                field: "Engine".to_string(),
+                old: Some("XNA".to_string()),
+                new: Some("FNA".to_string()),
+            }])
+        );
+        assert_eq!(changes[1].name, "third");
+        assert_eq!(changes[1].kind, ChangeKind::Added);
+        assert_eq!(changes[2].name, "second");
+        assert_eq!(changes[2].kind, ChangeKind::Removed);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::models::lint_config::RULES;
+use crate::models::{Diagnostic, LintConfig, Severity};
+use crate::utils::database_builder::DataBaseBuilder;
+use crate::utils::lint::lint_database;
+
+// Rules of the linter spotting the lines that would be lost
+// or altered by the formatter. Misplaced or missing fields,
+// alternative date formats and whitespaces are fixed by the
+// formatter, hence the other rules are not checked.
+const DATA_LOSS_RULES: [&str; 6] = [
+    "missing-name",
+    "extra-tab",
+    "duplicate-name",
+    "unknown-field",
+    "duplicate-field",
+    "invalid-date",
+];
+
+fn check_database(data: &str) -> Vec<Diagnostic> {
+    let mut config = LintConfig::new();
+    for (rule, _) in RULES {
+        let severity = if DATA_LOSS_RULES.contains(&rule) {
+            Severity::Error
+        } else {
+            Severity::Off
+        };
+        config.rules.insert(rule.to_string(), severity);
+    }
+    lint_database(data, &config)
+}
+
+/// Rewrite the database in its canonical form:
+/// - the fields of each game are given in the database order,
+///   all of them being present,
+/// - surrounding whitespaces and empty lines are removed,
+/// - stores are separated by a space, genres and tags by a comma
+///   followed by a space,
+/// - dates are written as YYYY-MM-DD.
+///
+/// The games are kept in the order of the input and their values
+/// are left untouched (no cover expansion, no Updated filling).
+/// If the input contains lines that cannot be formatted without
+/// losing data (unknown fields, duplicated fields or games,
+/// unparsable dates...), nothing is formatted and the problems
+/// are returned instead.
+pub fn format_database(data: &str) -> Result<String, Vec<Diagnostic>> {
+    let errors = check_database(data);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let database = DataBaseBuilder::verbatim().build_from_string(data.to_string());
+    let mut content = String::new();
+    for game in database.get_all_games().items {
+        content.push_str(&game.to_string());
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+// Split the lines of the database in blocks, each one
+// holding a Game line. Lines preceding the first game
+// belong to the first block.
+fn blocks(data: &str) -> Vec<Vec<&str>> {
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    let mut has_game = false;
+    for line in data.lines() {
+        let is_game = line.split('\t').next() == Some("Game");
+        if blocks.is_empty() || (is_game && has_game) {
+            blocks.push(Vec::new());
+        }
+        has_game |= is_game;
+        if let Some(block) = blocks.last_mut() {
+            block.push(line);
+        }
+    }
+    blocks
+}
+
+// Line diff of two blocks based on their longest common
+// subsequence. Blocks being small, the quadratic cost
+// is not an issue.
+fn diff_block(old: &[&str], new: &[&str], output: &mut String) {
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            output.push_str(&format!(" {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            output.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        } else {
+            output.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        }
+    }
+}
+
+/// Return the differences between the database and its
+/// formatted version, game by game. Each modified game is
+/// introduced by a `@@ line N: name @@` header, N being the
+/// line of the game in the original database.
+/// An empty string is returned when both are identical.
+pub fn format_diff(original: &str, formatted: &str) -> String {
+    let mut output = String::new();
+    let old_blocks = blocks(original);
+    let new_blocks = blocks(formatted);
+    let mut line = 1;
+    for (i, old) in old_blocks.iter().enumerate() {
+        let new = new_blocks
+            .get(i)
+            .map(|block| block.as_slice())
+            .unwrap_or(&[]);
+        if old.as_slice() != new {
+            let name = old
+                .iter()
+                .find_map(|line| line.strip_prefix("Game\t"))
+                .unwrap_or("")
+                .trim();
+            output.push_str(&format!("@@ line {}: {} @@\n", line, name));
+            diff_block(old, new, &mut output);
+        }
+        line += old.len();
+    }
+    for new in new_blocks.iter().skip(old_blocks.len()) {
+        output.push_str(&format!("@@ line {}: @@\n", line));
+        diff_block(&[], new, &mut output);
+    }
+    output
+}
+
+#[cfg(test)]
+mod test_formatter {
+    use super::*;
+    fn game(name: &str) -> String {
+        format!(
+            "Game\t{}\nCover\tcover.png\nEngine\nSetup\nRuntime\nStore\thttps://a.example.org https://b.example.org\nHints\nGenre\tRPG\nTags\tindie, free\nYear\nDev\nPub\nVersion\nStatus\nAdded\t2020-01-01\nUpdated\n",
+            name
+        )
+    }
+    #[test]
+    fn canonical_is_unchanged() {
+        let data = format!("{}{}", game("b"), game("a"));
+        assert_eq!(format_database(&data).unwrap(), data);
+        assert_eq!(format_diff(&data, &data), "");
+    }
+    #[test]
+    fn normalize() {
+        let data = "Game\tb \nEngine\tFNA\nCover\tcover.png\n\nStore\thttps://a.example.org  https://b.example.org\nGenre\tRPG\nTags\tindie,free,\nAdded\t2020/01/01\n"
+            .to_string()
+            + &game("a");
+        let expected = format!(
+            "{}{}",
+            game("b").replace("Engine\n", "Engine\tFNA\n"),
+            game("a")
+        );
+        let formatted = format_database(&data).unwrap();
+        assert_eq!(formatted, expected);
+        let diff = format_diff(&data, &formatted);
+        assert!(diff.starts_with("@@ line 1: b @@\n-Game\tb \n"));
+        assert!(diff.contains("+Game\tb\n"));
+        assert!(diff.contains("-Added\t2020/01/01\n"));
+        assert!(diff.contains("+Added\t2020-01-01\n"));
+        assert!(!diff.contains(": a @@"));
+    }
+    #[test]
+    fn refuse_data_loss() {
+        let data = game("a").replace("Hints\n", "Hints\nNotes\tsomething\n")
+            + &game("a").replace("Updated\n", "Updated\tlast week\n");
+        let errors = format_database(&data).unwrap_err();
+        let rules: Vec<&str> = errors.iter().map(|error| error.rule.as_str()).collect();
+        assert_eq!(
+            rules,
+            vec!["unknown-field", "duplicate-name", "invalid-date"]
+        );
+    }
+}
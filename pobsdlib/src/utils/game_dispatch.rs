@@ -1,28 +1,50 @@
+use crate::collections::BorrowedDataBase;
 use crate::collections::DataBase;
-use crate::models::{Field, Game, Item};
+use crate::models::{Field, FieldKind, Game, GameRef, Symbol};
 use crate::utils::database_builder::Cursor;
-use crate::utils::get_app_id;
+use crate::utils::{get_steam_cover, parse_date};
 use chrono::NaiveDate;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
 
-pub fn game_dispatch<'a>(
+// Old revisions of the database contain malformed dates,
+// hence they are skipped instead of panicking.
+fn parse_valid_date(date: &str) -> Option<NaiveDate> {
+    let parsed = parse_date(date);
+    if parsed.is_none() {
+        eprintln!("Skipping invalid date: {}", date);
+    }
+    parsed
+}
+
+// A single-valued field (engine, runtime, year, developer or
+// publisher) given twice keeps its last value: the game is
+// removed from the item of the previous one.
+fn unindex_previous(database: &mut DataBase, kind: FieldKind, uuid: u64) {
+    let previous = database
+        .games
+        .get(&uuid)
+        .and_then(|game| kind.values(game).first().map(|value| value.to_string()));
+    if let Some(previous) = previous {
+        database.unindex_value(kind, &previous, uuid);
+    }
+}
+
+pub fn game_dispatch(
     field: Field,
     database: &mut DataBase,
     // expand the cover to complete url
     expand_cover: bool,
     // fetch steam cover if possible
     steam_cover: bool,
-    cursor: &'a mut Cursor,
+    // use the added date when the updated date is missing
+    fill_updated: bool,
+    cursor: &mut Cursor,
 ) {
     match field {
         Field::Game(name) => {
             if let Some(name) = name {
                 cursor.counter += 1;
                 let mut game = Game::default();
-                let mut hasher = DefaultHasher::new();
-                name.hash(&mut hasher);
-                cursor.uuid = hasher.finish();
+                cursor.uuid = Game::compute_uuid(name);
                 game.name = name.to_string();
                 game.id = cursor.counter;
                 game.uuid = cursor.uuid;
@@ -52,17 +74,11 @@ pub fn game_dispatch<'a>(
         }
         Field::Engine(name) => {
             if let Some(name) = name {
+                unindex_previous(database, FieldKind::Engine, cursor.uuid);
+                let name = database.index_value(FieldKind::Engine, name, cursor.uuid);
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    game.engine = Some(name.to_string());
+                    game.engine = Some(name);
                 };
-                database
-                    .engines
-                    .entry(name.to_string())
-                    .and_modify(|e| e.games.push(cursor.uuid))
-                    .or_insert(Item {
-                        name: name.to_string(),
-                        games: vec![cursor.uuid],
-                    });
             };
         }
         Field::Setup(name) => {
@@ -74,17 +90,11 @@ pub fn game_dispatch<'a>(
         }
         Field::Runtime(name) => {
             if let Some(name) = name {
+                unindex_previous(database, FieldKind::Runtime, cursor.uuid);
+                let name = database.index_value(FieldKind::Runtime, name, cursor.uuid);
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    game.runtime = Some(name.to_string());
+                    game.runtime = Some(name);
                 };
-                database
-                    .runtimes
-                    .entry(name.to_string())
-                    .and_modify(|e| e.games.push(cursor.uuid))
-                    .or_insert(Item {
-                        name: name.to_string(),
-                        games: vec![cursor.uuid],
-                    });
             };
         }
         Field::Hints(name) => {
@@ -96,32 +106,20 @@ pub fn game_dispatch<'a>(
         }
         Field::Dev(name) => {
             if let Some(name) = name {
+                unindex_previous(database, FieldKind::Dev, cursor.uuid);
+                let name = database.index_value(FieldKind::Dev, name, cursor.uuid);
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    game.dev = Some(name.to_string());
+                    game.dev = Some(name);
                 };
-                database
-                    .devs
-                    .entry(name.to_string())
-                    .and_modify(|e| e.games.push(cursor.uuid))
-                    .or_insert(Item {
-                        name: name.to_string(),
-                        games: vec![cursor.uuid],
-                    });
             };
         }
         Field::Publi(name) => {
             if let Some(name) = name {
+                unindex_previous(database, FieldKind::Publi, cursor.uuid);
+                let name = database.index_value(FieldKind::Publi, name, cursor.uuid);
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    game.publi = Some(name.to_string());
+                    game.publi = Some(name);
                 };
-                database
-                    .publis
-                    .entry(name.to_string())
-                    .and_modify(|e| e.games.push(cursor.uuid))
-                    .or_insert(Item {
-                        name: name.to_string(),
-                        games: vec![cursor.uuid],
-                    });
             };
         }
         Field::Version(name) => {
@@ -145,14 +143,9 @@ pub fn game_dispatch<'a>(
                         // Tries to grap the Steam one
                         // if a steam link is given in store.
                         // if is_empty && item.contains("steampowered") {
-                        if item.contains("steampowered") && steam_cover {
-                            let item = item;
-                            let app_id = get_app_id(item);
-                            if let Some(app_id) = app_id {
-                                game.cover = Some(format!(
-                                    "https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg",
-                                    app_id
-                                ));
+                        if steam_cover {
+                            if let Some(cover) = get_steam_cover(item) {
+                                game.cover = Some(cover);
                             }
                         }
                         match &mut game.stores {
@@ -167,85 +160,51 @@ pub fn game_dispatch<'a>(
         }
         Field::Genres(items) => {
             if let Some(items) = items {
+                let items: Vec<Symbol> = items
+                    .iter()
+                    .map(|item| database.index_value(FieldKind::Genre, item, cursor.uuid))
+                    .collect();
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    for item in &items {
-                        match &mut game.genres {
-                            Some(genres) => genres.push(item.to_string()),
-                            None => {
-                                game.genres = Some(vec![item.to_string()]);
-                            }
-                        }
-                    }
+                    game.genres.get_or_insert_with(Vec::new).extend(items);
                 };
-                for item in &items {
-                    database
-                        .genres
-                        .entry(item.to_string())
-                        .and_modify(|e| e.games.push(cursor.uuid))
-                        .or_insert(Item {
-                            name: item.to_string(),
-                            games: vec![cursor.uuid],
-                        });
-                }
             };
         }
         Field::Tags(items) => {
             if let Some(items) = items {
+                let items: Vec<Symbol> = items
+                    .iter()
+                    .map(|item| database.index_value(FieldKind::Tag, item, cursor.uuid))
+                    .collect();
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    for item in &items {
-                        match &mut game.tags {
-                            Some(tags) => tags.push(item.to_string()),
-                            None => {
-                                game.tags = Some(vec![item.to_string()]);
-                            }
-                        }
-                    }
+                    game.tags.get_or_insert_with(Vec::new).extend(items);
                 };
-                for item in &items {
-                    database
-                        .tags
-                        .entry(item.to_string())
-                        .and_modify(|e| e.games.push(cursor.uuid))
-                        .or_insert(Item {
-                            name: item.to_string(),
-                            games: vec![cursor.uuid],
-                        });
-                }
             };
         }
         Field::Year(year) => {
             if let Some(year) = year {
+                unindex_previous(database, FieldKind::Year, cursor.uuid);
+                let year = database.index_value(FieldKind::Year, year, cursor.uuid);
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    game.year = Some(year.to_string());
+                    game.year = Some(year);
                 };
-                database
-                    .years
-                    .entry(year.to_string())
-                    .and_modify(|e| e.games.push(cursor.uuid))
-                    .or_insert(Item {
-                        name: year.to_string(),
-                        games: vec![cursor.uuid],
-                    });
             }
         }
         Field::Added(date) => {
             if let Some(date) = date {
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    game.added = Some(
-                        NaiveDate::parse_from_str(date, "%F").expect("fail to convert to date"),
-                    );
+                    game.added = parse_valid_date(date);
                 };
             }
         }
         Field::Updated(date) => {
             if let Some(date) = date {
                 if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                    game.updated = Some(
-                        NaiveDate::parse_from_str(date, "%F").expect("fail to convert to date"),
-                    );
+                    game.updated = parse_valid_date(date);
                 };
-            } else if let Some(game) = database.games.get_mut(&cursor.uuid) {
-                game.updated = game.added;
+            } else if fill_updated {
+                if let Some(game) = database.games.get_mut(&cursor.uuid) {
+                    game.updated = game.added;
+                }
             }
         }
         Field::Unknown(left, right) => {
@@ -262,17 +221,70 @@ pub fn game_dispatch<'a>(
     }
 }
 
+// Set the field of the current game of a borrowed database.
+// This is a separate implementation of game_dispatch, which
+// must be kept in sync with it, and it differs on purpose:
+// the values are kept as written (no cover expansion, no
+// Steam cover, no Updated filling, as with
+// DataBaseBuilder::verbatim) and the item collections are
+// filled once the text is read (see BorrowedDataBase::parse)
+// rather than line by line.
+pub(crate) fn game_ref_dispatch<'a>(
+    field: Field<'a>,
+    database: &mut BorrowedDataBase<'a>,
+    cursor: &mut Cursor,
+) {
+    if let Field::Game(Some(name)) = field {
+        cursor.counter += 1;
+        cursor.uuid = Game::compute_uuid(name);
+        database.games.insert(
+            cursor.uuid,
+            GameRef {
+                id: cursor.counter,
+                uuid: cursor.uuid,
+                name,
+                ..Default::default()
+            },
+        );
+        return;
+    }
+    let game = match database.games.get_mut(&cursor.uuid) {
+        Some(game) => game,
+        None => return,
+    };
+    match field {
+        Field::Cover(Some(cover)) if !cover.is_empty() => game.cover = Some(cover),
+        Field::Engine(Some(engine)) => game.engine = Some(engine),
+        Field::Setup(Some(setup)) => game.setup = Some(setup),
+        Field::Runtime(Some(runtime)) => game.runtime = Some(runtime),
+        Field::Store(Some(stores)) => game.stores.get_or_insert_with(Vec::new).extend(stores),
+        Field::Hints(Some(hints)) => game.hints = Some(hints),
+        Field::Genres(Some(genres)) => game.genres.get_or_insert_with(Vec::new).extend(genres),
+        Field::Tags(Some(tags)) => game.tags.get_or_insert_with(Vec::new).extend(tags),
+        Field::Year(Some(year)) => game.year = Some(year),
+        Field::Dev(Some(dev)) => game.dev = Some(dev),
+        Field::Publi(Some(publi)) => game.publi = Some(publi),
+        Field::Version(Some(version)) => game.version = Some(version),
+        Field::Status(Some(status)) => game.status = Some(status),
+        Field::Added(Some(added)) => game.added = parse_valid_date(added),
+        Field::Updated(Some(updated)) => game.updated = parse_valid_date(updated),
+        _ => (),
+    }
+}
+
 #[cfg(test)]
 mod test_game_dispatch {
     use super::*;
     use crate::collections::DataBase;
     use crate::models::Field;
+    use crate::models::Item;
+    use std::collections::BTreeSet;
     #[test]
     fn dispatch_game() {
         let mut cursor = Cursor::new();
         let mut db = DataBase::default();
         let fd = Field::Game(Some("test"));
-        game_dispatch(fd, &mut db, true, true, &mut cursor);
+        game_dispatch(fd, &mut db, true, true, true, &mut cursor);
         assert_eq!(db.games.len(), 1);
         assert_eq!(db.games.get(&cursor.uuid).unwrap().name, "test".to_string());
     }
@@ -282,8 +294,8 @@ mod test_game_dispatch {
         let mut db = DataBase::default();
         let fd = Field::Game(Some("test"));
         let co = Field::Cover(Some("cover"));
-        game_dispatch(fd, &mut db, true, true, &mut cursor);
-        game_dispatch(co, &mut db, true, true, &mut cursor);
+        game_dispatch(fd, &mut db, true, true, true, &mut cursor);
+        game_dispatch(co, &mut db, true, true, true, &mut cursor);
         assert_eq!(db.games.len(), 1);
         assert_eq!(
             db.games.get(&cursor.uuid).unwrap().cover.as_ref().unwrap(),
@@ -296,30 +308,47 @@ mod test_game_dispatch {
         let mut db = DataBase::default();
         let fd1 = Field::Game(Some("test1"));
         let fd2 = Field::Engine(Some("test2"));
-        game_dispatch(fd1, &mut db, true, true, &mut cursor);
-        game_dispatch(fd2, &mut db, true, true, &mut cursor);
+        game_dispatch(fd1, &mut db, true, true, true, &mut cursor);
+        game_dispatch(fd2, &mut db, true, true, true, &mut cursor);
         assert_eq!(db.games.len(), 1);
         assert_eq!(
             db.games.get(&cursor.uuid).unwrap().engine.as_ref().unwrap(),
             &"test2".to_string()
         );
-        assert_eq!(db.engines.len(), 1);
+        assert_eq!(db.facets[&FieldKind::Engine].len(), 1);
         assert_eq!(
-            db.engines.get("test2").unwrap(),
+            db.facets[&FieldKind::Engine].get("test2").unwrap(),
             &Item {
-                name: "test2".to_string(),
-                games: vec![cursor.uuid]
+                name: "test2".into(),
+                games: BTreeSet::from([cursor.uuid])
             }
         );
     }
     #[test]
+    fn dispatch_engine_twice() {
+        let mut cursor = Cursor::new();
+        let mut db = DataBase::default();
+        for field in [
+            Field::Game(Some("test1")),
+            Field::Engine(Some("XNA")),
+            Field::Engine(Some("FNA")),
+        ] {
+            game_dispatch(field, &mut db, true, true, true, &mut cursor);
+        }
+        assert_eq!(db.games_by(FieldKind::Engine, "XNA").count, 0);
+        assert_eq!(db.games_by(FieldKind::Engine, "FNA").count, 1);
+        db.remove_game(cursor.uuid);
+        assert_eq!(db.games_by(FieldKind::Engine, "XNA").count, 0);
+        assert_eq!(db.items(FieldKind::Engine).count, 0);
+    }
+    #[test]
     fn dispatch_setup() {
         let mut cursor = Cursor::new();
         let mut db = DataBase::default();
         let fd1 = Field::Game(Some("test1"));
         let fd2 = Field::Setup(Some("test2"));
-        game_dispatch(fd1, &mut db, true, true, &mut cursor);
-        game_dispatch(fd2, &mut db, true, true, &mut cursor);
+        game_dispatch(fd1, &mut db, true, true, true, &mut cursor);
+        game_dispatch(fd2, &mut db, true, true, true, &mut cursor);
         assert_eq!(db.games.len(), 1);
         assert_eq!(
             db.games.get(&cursor.uuid).unwrap().setup.as_ref().unwrap(),
@@ -332,8 +361,8 @@ mod test_game_dispatch {
         let mut db = DataBase::default();
         let fd1 = Field::Game(Some("test1"));
         let fd2 = Field::Runtime(Some("test2"));
-        game_dispatch(fd1, &mut db, true, true, &mut cursor);
-        game_dispatch(fd2, &mut db, true, true, &mut cursor);
+        game_dispatch(fd1, &mut db, true, true, true, &mut cursor);
+        game_dispatch(fd2, &mut db, true, true, true, &mut cursor);
         assert_eq!(db.games.len(), 1);
         assert_eq!(
             db.games
@@ -344,12 +373,12 @@ mod test_game_dispatch {
                 .unwrap(),
             &"test2".to_string()
         );
-        assert_eq!(db.runtimes.len(), 1);
+        assert_eq!(db.facets[&FieldKind::Runtime].len(), 1);
         assert_eq!(
-            db.runtimes.get("test2").unwrap(),
+            db.facets[&FieldKind::Runtime].get("test2").unwrap(),
             &Item {
-                name: "test2".to_string(),
-                games: vec![cursor.uuid]
+                name: "test2".into(),
+                games: BTreeSet::from([cursor.uuid])
             }
         );
     }
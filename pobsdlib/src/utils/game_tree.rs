@@ -0,0 +1,235 @@
+use crate::collections::DataBase;
+use crate::models::Game;
+use crate::record::GameRecord;
+use crate::utils::lint::FIELDS;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Content of a game file: the record of the game, its
+// uuid being computed when the tree is read, and its id
+// keeping the position of the game in the database.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct GameFile {
+    id: usize,
+    #[serde(flatten)]
+    record: GameRecord,
+}
+
+impl From<Game> for GameFile {
+    fn from(game: Game) -> Self {
+        GameFile {
+            id: game.id,
+            record: GameRecord::from(game),
+        }
+    }
+}
+
+impl From<GameFile> for Game {
+    fn from(file: GameFile) -> Self {
+        file.record.into_game(file.id)
+    }
+}
+
+impl GameFile {
+    // Parse a game file. Unknown keys are refused so that
+    // misspelled fields are not silently dropped.
+    fn parse(content: &str) -> Result<Self, String> {
+        let table: toml::Table = toml::from_str(content).map_err(|err| err.to_string())?;
+        if let Some(key) = table
+            .keys()
+            .find(|key| *key != "id" && !FIELDS.contains(&key.as_str()))
+        {
+            return Err(format!("unknown field: {}", key));
+        }
+        table.try_into().map_err(|err| err.to_string())
+    }
+}
+
+/// Return the file name of the game in the tree (without
+/// extension): the name of the game in lower case, the
+/// characters other than letters and digits being replaced
+/// by dashes.
+pub fn game_file_stem(name: &str) -> String {
+    let mut stem = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            stem.push(c);
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    let stem = stem.trim_end_matches('-');
+    if stem.is_empty() {
+        "_".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+// Directory of a game file: the first character of its
+// file name if it is an ASCII letter or digit, _ otherwise.
+fn game_directory(stem: &str) -> String {
+    match stem.chars().next() {
+        Some(c) if c.is_ascii_alphanumeric() => c.to_string(),
+        _ => "_".to_string(),
+    }
+}
+
+// Directories written by write_game_tree (see game_directory).
+fn is_game_directory(path: &Path) -> bool {
+    if !path.is_dir() {
+        return false;
+    }
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => c == '_' || c.is_ascii_lowercase() || c.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
+}
+
+fn invalid_data(path: &Path, message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), message),
+    )
+}
+
+/// Write each game of the database in its own TOML file,
+/// in one directory per first letter (e.g. `s/shuggy.toml`).
+/// Games whose file names collide get a numbered suffix.
+/// The TOML files already present in those directories (and
+/// only in those) are removed first, so that renamed or
+/// removed games do not linger in the tree.
+pub fn write_game_tree(database: &DataBase, dir: &Path) -> io::Result<()> {
+    if dir.is_dir() {
+        for entry in fs::read_dir(dir)? {
+            let subdir = entry?.path();
+            if !is_game_directory(&subdir) {
+                continue;
+            }
+            for file in fs::read_dir(&subdir)? {
+                let file = file?.path();
+                if is_toml(&file) {
+                    fs::remove_file(file)?;
+                }
+            }
+        }
+    }
+    let mut written: Vec<PathBuf> = Vec::new();
+    for game in database.get_all_games().items {
+        let stem = game_file_stem(&game.name);
+        let subdir = dir.join(game_directory(&stem));
+        let mut path = subdir.join(format!("{}.toml", stem));
+        let mut suffix = 2;
+        while written.contains(&path) {
+            path = subdir.join(format!("{}-{}.toml", stem, suffix));
+            suffix += 1;
+        }
+        let content = toml::to_string(&GameFile::from(game))
+            .map_err(|err| invalid_data(&path, err.to_string()))?;
+        fs::create_dir_all(&subdir)?;
+        fs::write(&path, content)?;
+        written.push(path);
+    }
+    Ok(())
+}
+
+/// Read the games written by write_game_tree. The games are
+/// ordered by id, as in the database they were written from,
+/// games sharing an id (e.g. added by hand) being ordered by
+/// name (see Game::sort_key). The ids are then renumbered.
+/// Converting a database to a tree and back is lossless.
+pub fn read_game_tree(dir: &Path) -> io::Result<DataBase> {
+    let mut games: Vec<Game> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let subdir = entry?.path();
+        if !is_game_directory(&subdir) {
+            continue;
+        }
+        for file in fs::read_dir(&subdir)? {
+            let path = file?.path();
+            if !is_toml(&path) {
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            let file = GameFile::parse(&content).map_err(|err| invalid_data(&path, err))?;
+            games.push(Game::from(file));
+        }
+    }
+    games.sort_by(|a, b| {
+        a.id.cmp(&b.id)
+            .then_with(|| Game::sort_key(&a.name).cmp(&Game::sort_key(&b.name)))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    for (index, game) in games.iter_mut().enumerate() {
+        game.id = index + 1;
+    }
+    Ok(DataBase::from_games(games))
+}
+
+#[cfg(test)]
+mod test_game_tree {
+    use super::*;
+    use chrono::NaiveDate;
+    #[test]
+    fn file_stem() {
+        assert_eq!(
+            game_file_stem("The Adventures of Shuggy"),
+            "the-adventures-of-shuggy"
+        );
+        assert_eq!(
+            game_file_stem("AaaaaAAaaaAAAaaAAAAaAAAAA!!! for the Awesome"),
+            "aaaaaaaaaaaaaaaaaaaaaaaaa-for-the-awesome"
+        );
+        assert_eq!(game_file_stem("!!!"), "_");
+        assert_eq!(game_directory("the-adventures-of-shuggy"), "t");
+        assert_eq!(game_directory("éa"), "_");
+    }
+    #[test]
+    fn other_files_are_kept() {
+        let dir = std::env::temp_dir().join(format!("pobsdlib-tree-keep-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub").join("Cargo.toml"), "[package]\n").unwrap();
+        let database = DataBase::from_games(vec![Game {
+            id: 1,
+            name: "Shuggy".to_string(),
+            ..Default::default()
+        }]);
+        write_game_tree(&database, &dir).unwrap();
+        write_game_tree(&database, &dir).unwrap();
+        assert!(dir.join("sub").join("Cargo.toml").exists());
+        assert_eq!(read_game_tree(&dir).unwrap().get_all_games().count, 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn toml_file() {
+        let game = Game {
+            id: 3,
+            name: "Shuggy".to_string(),
+            tags: Some(vec!["indie".into()]),
+            added: NaiveDate::from_ymd_opt(2020, 1, 1),
+            ..Default::default()
+        };
+        let content = toml::to_string(&GameFile::from(game.clone())).unwrap();
+        assert_eq!(
+            content,
+            "id = 3\nGame = \"Shuggy\"\nTags = [\"indie\"]\nAdded = \"2020-01-01\"\n"
+        );
+        assert_eq!(GameFile::parse(&content).unwrap(), GameFile::from(game));
+        assert_eq!(
+            GameFile::parse("id = 1\nGame = \"a\"\nColour = \"red\"\n").unwrap_err(),
+            "unknown field: Colour"
+        );
+        assert!(GameFile::parse("Game = \"a\"\n").is_err());
+    }
+}
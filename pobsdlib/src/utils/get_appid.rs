@@ -12,6 +12,20 @@ pub fn get_app_id(url: &str) -> Option<&str> {
     }
 }
 
+/// Return the url of the header picture of the
+/// game when a Steam store url is given.
+pub fn get_steam_cover(url: &str) -> Option<String> {
+    if !url.contains("steampowered") {
+        return None;
+    }
+    get_app_id(url).map(|app_id| {
+        format!(
+            "https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg",
+            app_id
+        )
+    })
+}
+
 #[cfg(test)]
 mod test_get_app_id {
     use super::*;
@@ -30,4 +44,13 @@ mod test_get_app_id {
         let url = "https://store.steampowered.com/";
         assert_eq!(get_app_id(url), None);
     }
+    #[test]
+    fn steam_cover() {
+        let url = "https://store.steampowered.com/app/245390";
+        assert_eq!(
+            get_steam_cover(url).unwrap(),
+            "https://cdn.akamai.steamstatic.com/steam/apps/245390/header.jpg"
+        );
+        assert_eq!(get_steam_cover("https://www.gog.com/app/1"), None);
+    }
 }
@@ -0,0 +1,61 @@
+use crate::models::Commit;
+use chrono::DateTime;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+fn run_git(repo: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    String::from_utf8(output.stdout).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Return the commits of the current branch modifying the
+/// given file of the git repository, from the oldest to the
+/// newest. Only the first parent of merges is followed.
+pub fn git_log(repo: &Path, file: &str) -> io::Result<Vec<Commit>> {
+    let log = run_git(
+        repo,
+        &[
+            "log",
+            "--first-parent",
+            "--reverse",
+            "--format=%H%x09%an%x09%aI",
+            "--",
+            file,
+        ],
+    )?;
+    let mut commits: Vec<Commit> = Vec::new();
+    for line in log.lines() {
+        let parts: Vec<&str> = line.splitn(3, '\t').collect();
+        if parts.len() != 3 {
+            eprintln!("Skipping malformed git log line: {}", line);
+            continue;
+        }
+        match DateTime::parse_from_rfc3339(parts[2]) {
+            Ok(date) => commits.push(Commit {
+                id: parts[0].to_string(),
+                author: parts[1].to_string(),
+                date,
+            }),
+            Err(_) => eprintln!("Skipping commit {} with invalid date", parts[0]),
+        }
+    }
+    Ok(commits)
+}
+
+/// Return the content of the given file at the given
+/// revision (commit id, branch, tag...) of the git repository.
+pub fn git_show(repo: &Path, rev: &str, file: &str) -> io::Result<String> {
+    run_git(repo, &["show", &format!("{}:{}", rev, file)])
+}
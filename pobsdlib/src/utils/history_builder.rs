@@ -0,0 +1,45 @@
+use crate::collections::{DataBase, History};
+use crate::utils::database_builder::DataBaseBuilder;
+use crate::utils::{diff_databases, git_log, git_show};
+use std::io;
+use std::path::PathBuf;
+
+/// Build the History of the database by walking the
+/// commits of a local clone of the
+/// `https://github.com/playonbsd/OpenBSD-Games-Database`
+/// repository. Only the first parent of each merge is
+/// followed, so that each commit is compared with the
+/// previous state of the main branch.
+pub struct HistoryBuilder {
+    repo: PathBuf,
+    file: String,
+}
+
+impl HistoryBuilder {
+    pub fn new<P: Into<PathBuf>>(repo: P) -> Self {
+        Self {
+            repo: repo.into(),
+            file: "openbsd-games.db".to_string(),
+        }
+    }
+    /// Set the path of the database inside the repository.
+    pub fn set_file(&mut self, file: &str) {
+        self.file = file.to_string();
+    }
+    pub fn build(self) -> io::Result<History> {
+        let mut history = History::default();
+        let mut previous = DataBase::default();
+        for commit in git_log(&self.repo, &self.file)? {
+            // the file can be missing from a commit, in
+            // which case all games are considered removed
+            let current = match git_show(&self.repo, &commit.id, &self.file) {
+                Ok(content) => DataBaseBuilder::new(false, false).build_from_string(content),
+                Err(_) => DataBase::default(),
+            };
+            let changes = diff_databases(&previous, &current);
+            history.push(commit, changes, &current);
+            previous = current;
+        }
+        Ok(history)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::models::Game;
+
+/// Insert the game in the database, right before the first
+/// game whose name comes after its name (see Game::sort_key),
+/// or at the end of the database. The other lines are left
+/// untouched.
+pub fn insert_game(data: &str, game: &Game) -> String {
+    let name = Game::sort_key(&game.name);
+    let mut content = String::with_capacity(data.len() + 256);
+    let mut inserted = false;
+    for line in data.lines() {
+        if !inserted {
+            if let Some(other) = line.strip_prefix("Game\t") {
+                if Game::sort_key(other) > name {
+                    content.push_str(&game.to_string());
+                    content.push('\n');
+                    inserted = true;
+                }
+            }
+        }
+        content.push_str(line);
+        content.push('\n');
+    }
+    if !inserted {
+        content.push_str(&game.to_string());
+        content.push('\n');
+    }
+    content
+}
+
+#[cfg(test)]
+mod test_insert_game {
+    use super::*;
+    fn game(name: &str) -> Game {
+        Game {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+    #[test]
+    fn alphabetical_position() {
+        let data = format!("{}\n{}\n", game("aeternum"), game("Shuggy"));
+        let content = insert_game(&data, &game("Jazz"));
+        assert_eq!(
+            content,
+            format!(
+                "{}\n{}\n{}\n",
+                game("aeternum"),
+                game("Jazz"),
+                game("Shuggy")
+            )
+        );
+        let content = insert_game(&data, &game("Zork"));
+        assert_eq!(content, format!("{}{}\n", data, game("Zork")));
+    }
+    #[test]
+    fn leading_article() {
+        let data = format!(
+            "{}\n{}\n{}\n",
+            game("The Adventures of Mr. Hat"),
+            game("Alien Shepherd"),
+            game("Always Sometimes Monsters")
+        );
+        let content = insert_game(&data, &game("Alien Zoo"));
+        assert_eq!(
+            content,
+            format!(
+                "{}\n{}\n{}\n{}\n",
+                game("The Adventures of Mr. Hat"),
+                game("Alien Shepherd"),
+                game("Alien Zoo"),
+                game("Always Sometimes Monsters")
+            )
+        );
+    }
+}
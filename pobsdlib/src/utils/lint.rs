@@ -0,0 +1,407 @@
+use crate::models::{Diagnostic, FieldKind, LintConfig, Severity, TermIssue};
+use crate::utils::parse_date;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Keys of the lines describing a game, in the database order.
+pub const FIELDS: [&str; 16] = [
+    "Game", "Cover", "Engine", "Setup", "Runtime", "Store", "Hints", "Genre", "Tags", "Year",
+    "Dev", "Pub", "Version", "Status", "Added", "Updated",
+];
+
+// Lines of the game being checked.
+#[derive(Default)]
+struct Block<'a> {
+    name: Option<&'a str>,
+    line: usize,
+    keys: Vec<(&'a str, usize)>,
+    added: Option<NaiveDate>,
+    updated: Option<(NaiveDate, usize)>,
+}
+
+struct Linter<'a> {
+    config: &'a LintConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    fn report(&mut self, rule: &str, line: usize, game: Option<&str>, message: String) {
+        let severity = self.config.severity(rule);
+        if severity != Severity::Off {
+            self.diagnostics.push(Diagnostic {
+                rule: rule.to_string(),
+                severity,
+                line,
+                game: game.map(|game| game.to_string()),
+                message,
+            });
+        }
+    }
+    fn check_block(&mut self, block: &Block) {
+        let name = block.name;
+        for field in FIELDS {
+            if !block.keys.iter().any(|(key, _)| *key == field) {
+                self.report(
+                    "missing-field",
+                    block.line,
+                    name,
+                    format!("missing {} line", field),
+                );
+            }
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        for (key, line) in &block.keys {
+            if seen.contains(key) {
+                self.report(
+                    "duplicate-field",
+                    *line,
+                    name,
+                    format!("{} line given twice", key),
+                );
+            }
+            seen.push(key);
+        }
+        let mut last = 0;
+        for (key, line) in &block.keys {
+            if let Some(position) = FIELDS.iter().position(|field| field == key) {
+                if position < last {
+                    self.report(
+                        "field-order",
+                        *line,
+                        name,
+                        format!("{} line should come before {} line", key, FIELDS[last]),
+                    );
+                }
+                last = last.max(position);
+            }
+        }
+        if let (Some(added), Some((updated, line))) = (block.added, block.updated) {
+            if updated < added {
+                self.report(
+                    "updated-before-added",
+                    line,
+                    name,
+                    format!("updated on {} before being added on {}", updated, added),
+                );
+            }
+        }
+    }
+    fn check_items(&mut self, key: &str, value: &str, line: usize, game: Option<&str>) {
+        let (separator, kind, rule) = match key {
+            "Genre" => (',', FieldKind::Genre, "unknown-genre"),
+            "Tags" => (',', FieldKind::Tag, "unknown-tag"),
+            _ => return,
+        };
+        let mut issues: Vec<(String, TermIssue)> = Vec::new();
+        let mut empty = false;
+        for item in value.split(separator).map(|item| item.trim()) {
+            if item.is_empty() {
+                empty = true;
+            } else if let Some(issue) = self.config.vocabulary.check(kind, item) {
+                issues.push((item.to_string(), issue));
+            }
+        }
+        if empty {
+            self.report(
+                "empty-item",
+                line,
+                game,
+                format!("empty item in {} line", key),
+            );
+        }
+        for (item, issue) in issues {
+            match issue {
+                TermIssue::Unknown { suggestion: None } => {
+                    self.report(rule, line, game, format!("unknown {}: {}", key, item))
+                }
+                TermIssue::Unknown {
+                    suggestion: Some(suggestion),
+                } => self.report(
+                    rule,
+                    line,
+                    game,
+                    format!("unknown {}: {} (did you mean {}?)", key, item, suggestion),
+                ),
+                TermIssue::Deprecated { replacement } => self.report(
+                    "deprecated-term",
+                    line,
+                    game,
+                    format!("deprecated {}: {} (use {} instead)", key, item, replacement),
+                ),
+            }
+        }
+    }
+    fn check_stores(&mut self, value: &str, line: usize, game: Option<&str>) {
+        for url in value.split(' ') {
+            if url.is_empty() {
+                self.report(
+                    "empty-item",
+                    line,
+                    game,
+                    "empty item in Store line".to_string(),
+                );
+            } else if !is_url(url) {
+                self.report(
+                    "store-url",
+                    line,
+                    game,
+                    format!("malformed store url: {}", url),
+                );
+            }
+        }
+    }
+    fn check_date(
+        &mut self,
+        key: &str,
+        value: &str,
+        line: usize,
+        game: Option<&str>,
+    ) -> Option<NaiveDate> {
+        match parse_date(value) {
+            Some(date) => {
+                if NaiveDate::parse_from_str(value, "%F").is_err() {
+                    self.report(
+                        "date-format",
+                        line,
+                        game,
+                        format!("{} date is not a YYYY-MM-DD date: {}", key, value),
+                    );
+                }
+                Some(date)
+            }
+            None => {
+                self.report(
+                    "invalid-date",
+                    line,
+                    game,
+                    format!("{} date cannot be parsed: {}", key, value),
+                );
+                None
+            }
+        }
+    }
+}
+
+// A store url is expected to be an http(s) url with a host.
+fn is_url(url: &str) -> bool {
+    let rest = match url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let host = rest.split('/').next().unwrap_or("");
+    !host.is_empty() && host.contains('.') && !url.contains(char::is_whitespace)
+}
+
+/// Check the database against the rules of the linter
+/// and return the problems found, ordered by line.
+pub fn lint_database(data: &str, config: &LintConfig) -> Vec<Diagnostic> {
+    let mut linter = Linter {
+        config,
+        diagnostics: Vec::new(),
+    };
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    let mut block: Option<Block> = None;
+    for (index, line) in data.lines().enumerate() {
+        let number = index + 1;
+        let game = block.as_ref().and_then(|block| block.name);
+        if line.ends_with(' ') || line.ends_with('\t') {
+            linter.report(
+                "trailing-whitespace",
+                number,
+                game,
+                "trailing whitespace".to_string(),
+            );
+        }
+        let columns: Vec<&str> = line.split('\t').collect();
+        if columns.len() > 2 {
+            linter.report(
+                "extra-tab",
+                number,
+                game,
+                format!("{} tab separated columns instead of 2", columns.len()),
+            );
+        }
+        let key = columns[0];
+        let value = columns.get(1).map(|value| value.trim()).unwrap_or("");
+        if key == "Game" {
+            if let Some(block) = &block {
+                linter.check_block(block);
+            }
+            let name = if value.is_empty() { None } else { Some(value) };
+            match name {
+                Some(name) => {
+                    if let Some(first) = names.get(name) {
+                        linter.report(
+                            "duplicate-name",
+                            number,
+                            Some(name),
+                            format!("game already defined line {}", first),
+                        );
+                    } else {
+                        names.insert(name, number);
+                    }
+                }
+                None => linter.report(
+                    "missing-name",
+                    number,
+                    None,
+                    "game without name".to_string(),
+                ),
+            }
+            block = Some(Block {
+                name,
+                line: number,
+                keys: vec![("Game", number)],
+                ..Default::default()
+            });
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let current = match block.as_mut() {
+            Some(current) => current,
+            None => {
+                linter.report(
+                    "unknown-field",
+                    number,
+                    None,
+                    "line outside of a game".to_string(),
+                );
+                continue;
+            }
+        };
+        if !FIELDS.contains(&key) {
+            linter.report(
+                "unknown-field",
+                number,
+                game,
+                format!("unknown field: {}", key),
+            );
+            continue;
+        }
+        current.keys.push((key, number));
+        if value.is_empty() {
+            continue;
+        }
+        match key {
+            "Genre" | "Tags" => linter.check_items(key, value, number, game),
+            "Store" => linter.check_stores(value, number, game),
+            "Added" => current.added = linter.check_date(key, value, number, game),
+            "Updated" => {
+                current.updated = linter
+                    .check_date(key, value, number, game)
+                    .map(|date| (date, number))
+            }
+            _ => {}
+        }
+    }
+    if let Some(block) = &block {
+        linter.check_block(block);
+    }
+    let mut diagnostics = linter.diagnostics;
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    diagnostics
+}
+
+#[cfg(test)]
+mod test_lint {
+    use super::*;
+    use crate::models::Term;
+    fn game(name: &str) -> String {
+        format!(
+            "Game\t{}\nCover\nEngine\nSetup\nRuntime\nStore\thttps://store.steampowered.com/app/1\nHints\nGenre\tRPG\nTags\tindie, free\nYear\nDev\nPub\nVersion\nStatus\nAdded\t2020-01-01\nUpdated\t2021-01-01\n",
+            name
+        )
+    }
+    fn rules(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.rule.as_str())
+            .collect()
+    }
+    #[test]
+    fn valid_database() {
+        let data = format!("{}{}", game("first"), game("second"));
+        assert!(lint_database(&data, &LintConfig::new()).is_empty());
+    }
+    #[test]
+    fn invalid_game() {
+        let data = game("first")
+            .replace("Year\nDev\n", "Dev\nYear\n")
+            .replace("Status\n", "")
+            .replace("Added\t2020-01-01", "Added\t2022-01-01")
+            .replace("Updated\t2021-01-01", "Updated\t2021/01/01 ")
+            .replace("app/1", "app/1 store.example.org")
+            + "Hints\tagain\tand again\n";
+        let diagnostics = lint_database(&data, &LintConfig::new());
+        assert_eq!(
+            rules(&diagnostics),
+            vec![
+                "missing-field",
+                "store-url",
+                "field-order",
+                "trailing-whitespace",
+                "date-format",
+                "updated-before-added",
+                "extra-tab",
+                "duplicate-field",
+                "field-order",
+            ]
+        );
+        assert_eq!(diagnostics[0].line, 1);
+        assert_eq!(diagnostics[0].game.as_ref().unwrap(), "first");
+    }
+    #[test]
+    fn updated_before_added() {
+        let data = game("first").replace("Added\t2020-01-01", "Added\t2022-01-01");
+        let diagnostics = lint_database(&data, &LintConfig::new());
+        assert_eq!(rules(&diagnostics), vec!["updated-before-added"]);
+        assert_eq!(diagnostics[0].line, 16);
+    }
+    #[test]
+    fn duplicate_name() {
+        let data = format!("{}{}", game("first"), game("first"));
+        let diagnostics = lint_database(&data, &LintConfig::new());
+        assert_eq!(rules(&diagnostics), vec!["duplicate-name"]);
+        assert_eq!(diagnostics[0].line, 17);
+    }
+    #[test]
+    fn vocabulary_and_severity() {
+        let mut config = LintConfig::new();
+        config
+            .vocabulary
+            .tags
+            .insert("indie".to_string(), Term::default());
+        let diagnostics = lint_database(&game("first"), &config);
+        assert_eq!(rules(&diagnostics), vec!["unknown-tag"]);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        config
+            .rules
+            .insert("unknown-tag".to_string(), Severity::Off);
+        assert!(lint_database(&game("first"), &config).is_empty());
+    }
+    #[test]
+    fn deprecated_term() {
+        let mut config = LintConfig::new();
+        let gratis = Term {
+            deprecated: vec!["free".to_string()],
+            ..Default::default()
+        };
+        config
+            .vocabulary
+            .tags
+            .insert("indie".to_string(), Term::default());
+        config.vocabulary.tags.insert("gratis".to_string(), gratis);
+        let diagnostics = lint_database(&game("first"), &config);
+        assert_eq!(rules(&diagnostics), vec!["deprecated-term"]);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].message,
+            "deprecated Tags: free (use gratis instead)"
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use crate::collections::DataBase;
+use crate::models::{Field, Game};
+use crate::utils::database_builder::DataBaseBuilder;
+use std::collections::HashMap;
+
+const OURS_MARKER: &str = "<<<<<<< ours";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>> theirs";
+
+/// The MergeResult struct represents the outcome
+/// of a three-way merge of the database.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct MergeResult {
+    /// merged database, including conflict markers if any.
+    pub content: String,
+    /// number of conflicts left in the merged database.
+    pub conflicts: usize,
+}
+
+fn field_line(key: &str, value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("{}\t{}", key, value),
+        None => key.to_string(),
+    }
+}
+
+fn game_lines(game: &Game) -> Vec<String> {
+    game.field_values()
+        .iter()
+        .map(|(key, value)| field_line(key, value))
+        .collect()
+}
+
+// The text of a database split by game: the lines
+// preceding the first game and the lines of each game
+// (keyed by its uuid) as written in the database.
+struct GameTexts {
+    preamble: String,
+    games: HashMap<u64, String>,
+}
+
+impl GameTexts {
+    fn new(content: &str) -> Self {
+        let mut preamble = String::new();
+        let mut games: HashMap<u64, String> = HashMap::new();
+        let mut current: Option<u64> = None;
+        for line in content.split_inclusive('\n') {
+            if let Field::Game(Some(name)) = Field::from(line.trim_end_matches(['\n', '\r'])) {
+                let uuid = Game::compute_uuid(name);
+                games.insert(uuid, String::new());
+                current = Some(uuid);
+            }
+            let text = match current {
+                Some(uuid) => games.get_mut(&uuid).unwrap(),
+                None => &mut preamble,
+            };
+            text.push_str(line);
+            if !line.ends_with('\n') {
+                text.push('\n');
+            }
+        }
+        Self { preamble, games }
+    }
+    fn get(&self, uuid: &u64) -> Option<&str> {
+        self.games.get(uuid).map(String::as_str)
+    }
+}
+
+// Return the text of a game present on both sides when at
+// most one side changed it, None when both sides changed it.
+fn unchanged_text<'a>(base: Option<&'a str>, ours: &'a str, theirs: &'a str) -> Option<&'a str> {
+    if ours == theirs || Some(theirs) == base {
+        Some(ours)
+    } else if Some(ours) == base {
+        Some(theirs)
+    } else {
+        None
+    }
+}
+
+// The id depends on the position in the database,
+// hence it is not taken into account.
+fn same_game(a: &Game, b: &Game) -> bool {
+    a.field_values() == b.field_values()
+}
+
+// The games are given in the order of ours. The games
+// only present in theirs are placed right after the game
+// preceding them in theirs.
+fn merge_order(ours: &DataBase, theirs: &DataBase) -> Vec<u64> {
+    let mut order: Vec<u64> = ours
+        .get_all_games()
+        .items
+        .iter()
+        .map(|game| game.uuid)
+        .collect();
+    let theirs_order: Vec<u64> = theirs
+        .get_all_games()
+        .items
+        .iter()
+        .map(|game| game.uuid)
+        .collect();
+    for (i, uuid) in theirs_order.iter().enumerate() {
+        if ours.games.contains_key(uuid) {
+            continue;
+        }
+        let position = theirs_order[..i]
+            .iter()
+            .rev()
+            .find_map(|previous| order.iter().position(|placed| placed == previous))
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        order.insert(position, *uuid);
+    }
+    order
+}
+
+fn merge_game(base: Option<&Game>, ours: &Game, theirs: &Game, result: &mut MergeResult) {
+    let base = base.map(|game| game.field_values());
+    let theirs = theirs.field_values();
+    for (i, (key, ours)) in ours.field_values().iter().enumerate() {
+        let theirs = &theirs[i].1;
+        let base = base.as_ref().map(|base| &base[i].1);
+        let value = if ours == theirs || Some(theirs) == base {
+            ours
+        } else if Some(ours) == base {
+            theirs
+        } else if *key == "Updated" {
+            // dates are formatted as YYYY-MM-DD
+            ours.max(theirs)
+        } else {
+            result.conflicts += 1;
+            for line in [
+                OURS_MARKER.to_string(),
+                field_line(key, ours),
+                SEPARATOR_MARKER.to_string(),
+                field_line(key, theirs),
+                THEIRS_MARKER.to_string(),
+            ] {
+                result.content.push_str(&line);
+                result.content.push('\n');
+            }
+            continue;
+        };
+        result.content.push_str(&field_line(key, value));
+        result.content.push('\n');
+    }
+}
+
+// One side modified a game deleted by the other side.
+fn conflict_on_deletion(ours: Option<&Game>, theirs: Option<&Game>, result: &mut MergeResult) {
+    result.conflicts += 1;
+    let mut lines = vec![OURS_MARKER.to_string()];
+    if let Some(ours) = ours {
+        lines.extend(game_lines(ours));
+    }
+    lines.push(SEPARATOR_MARKER.to_string());
+    if let Some(theirs) = theirs {
+        lines.extend(game_lines(theirs));
+    }
+    lines.push(THEIRS_MARKER.to_string());
+    for line in lines {
+        result.content.push_str(&line);
+        result.content.push('\n');
+    }
+}
+
+/// Perform a three-way merge of the database, game by game
+/// and field by field. Games are identified by their name.
+///
+/// The following cases are resolved automatically:
+/// - games added on one side or on both sides (when identical),
+/// - games removed on one side and left untouched on the other,
+/// - fields modified on one side only or identically on both sides,
+/// - diverging Updated fields, the most recent date being kept.
+///
+/// The remaining conflicts are surrounded by conflict markers,
+/// either around the conflicting field or around the whole game
+/// when it was modified on one side and removed on the other.
+///
+/// Games left untouched on at least one side are copied
+/// as written in the database.
+pub fn merge_databases(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let base_texts = GameTexts::new(base);
+    let ours_texts = GameTexts::new(ours);
+    let theirs_texts = GameTexts::new(theirs);
+    let base = DataBaseBuilder::verbatim().build_from_string(base.to_string());
+    let ours = DataBaseBuilder::verbatim().build_from_string(ours.to_string());
+    let theirs = DataBaseBuilder::verbatim().build_from_string(theirs.to_string());
+    let mut result = MergeResult::default();
+    result.content.push_str(&ours_texts.preamble);
+    for uuid in merge_order(&ours, &theirs) {
+        match (
+            base.games.get(&uuid),
+            ours.games.get(&uuid),
+            theirs.games.get(&uuid),
+        ) {
+            (base, Some(ours), Some(theirs)) => match unchanged_text(
+                base_texts.get(&uuid),
+                ours_texts.get(&uuid).unwrap(),
+                theirs_texts.get(&uuid).unwrap(),
+            ) {
+                Some(text) => result.content.push_str(text),
+                None => merge_game(base, ours, theirs, &mut result),
+            },
+            (Some(base), Some(ours), None) => {
+                if !same_game(base, ours) {
+                    conflict_on_deletion(Some(ours), None, &mut result);
+                }
+            }
+            (Some(base), None, Some(theirs)) => {
+                if !same_game(base, theirs) {
+                    conflict_on_deletion(None, Some(theirs), &mut result);
+                }
+            }
+            (None, Some(_), None) => result.content.push_str(ours_texts.get(&uuid).unwrap()),
+            (None, None, Some(_)) => result.content.push_str(theirs_texts.get(&uuid).unwrap()),
+            (_, None, None) => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test_merge {
+    use super::*;
+    use std::fs;
+    fn game(name: &str, engine: &str, updated: &str) -> String {
+        format!(
+            "Game\t{}\nCover\nEngine\t{}\nSetup\nRuntime\nStore\nHints\nGenre\nTags\nYear\nDev\nPub\nVersion\nStatus\nAdded\t2020-01-01\nUpdated\t{}\n",
+            name, engine, updated
+        )
+    }
+    #[test]
+    fn both_added_different_games() {
+        let base = game("b", "XNA", "2020-01-01");
+        let ours = format!("{}{}", game("a", "FNA", "2020-01-01"), base);
+        let theirs = format!("{}{}", base, game("c", "FNA", "2020-01-01"));
+        let result = merge_databases(&base, &ours, &theirs);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(
+            result.content,
+            format!(
+                "{}{}{}",
+                game("a", "FNA", "2020-01-01"),
+                base,
+                game("c", "FNA", "2020-01-01")
+            )
+        );
+    }
+    #[test]
+    fn disjoint_edits_and_updated() {
+        let base = game("a", "XNA", "2020-01-01");
+        let ours = game("a", "FNA", "2021-01-01");
+        let theirs = game("a", "XNA", "2022-01-01").replace("Year\n", "Year\t2012\n");
+        let result = merge_databases(&base, &ours, &theirs);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(
+            result.content,
+            game("a", "FNA", "2022-01-01").replace("Year\n", "Year\t2012\n")
+        );
+    }
+    #[test]
+    fn conflicting_field() {
+        let base = game("a", "XNA", "2020-01-01");
+        let ours = game("a", "FNA", "2020-01-01");
+        let theirs = game("a", "MonoGame", "2020-01-01");
+        let result = merge_databases(&base, &ours, &theirs);
+        assert_eq!(result.conflicts, 1);
+        assert!(result
+            .content
+            .contains("<<<<<<< ours\nEngine\tFNA\n=======\nEngine\tMonoGame\n>>>>>>> theirs\n"));
+    }
+    #[test]
+    fn deletions() {
+        let base = format!(
+            "{}{}",
+            game("a", "XNA", "2020-01-01"),
+            game("b", "XNA", "2020-01-01")
+        );
+        // a is removed by ours, b is removed by theirs but modified by ours
+        let ours = game("b", "FNA", "2020-01-01");
+        let theirs = game("a", "XNA", "2020-01-01");
+        let result = merge_databases(&base, &ours, &theirs);
+        assert_eq!(result.conflicts, 1);
+        assert_eq!(
+            result.content,
+            format!(
+                "<<<<<<< ours\n{}=======\n>>>>>>> theirs\n",
+                game("b", "FNA", "2020-01-01")
+            )
+        );
+    }
+    #[test]
+    fn merge_with_itself() {
+        let content = fs::read_to_string("../db/openbsd-games.db").unwrap();
+        let result = merge_databases(&content, &content, &content);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(result.content, content);
+    }
+    #[test]
+    fn untouched_games_are_copied() {
+        // partial games and unknown lines are kept as written
+        let kept = "Game\ta\nEngine\tXNA\nFoo\tbar\n";
+        let base = format!("{}{}", kept, game("b", "XNA", "2020-01-01"));
+        let ours = format!("{}{}", kept, game("b", "FNA", "2020-01-01"));
+        let result = merge_databases(&base, &ours, &base);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(result.content, ours);
+    }
+}
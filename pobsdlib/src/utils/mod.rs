@@ -1,10 +1,38 @@
+pub use crate::utils::bump_updated::{bump_updated, BumpResult};
+pub use crate::utils::csv_io::{
+    game_column, read_games, write_facets, write_games, write_rows, CsvOptions,
+};
+pub use crate::utils::database_builder::GameReader;
+pub use crate::utils::database_diff::diff_databases;
+pub use crate::utils::formatter::{format_database, format_diff};
 pub use crate::utils::game_dispatch::game_dispatch;
-pub use crate::utils::get_appid::get_app_id;
+pub use crate::utils::game_tree::{game_file_stem, read_game_tree, write_game_tree};
+pub use crate::utils::get_appid::{get_app_id, get_steam_cover};
+pub use crate::utils::git::{git_log, git_show};
+pub use crate::utils::insert_game::insert_game;
+pub use crate::utils::lint::lint_database;
+pub use crate::utils::merge::{merge_databases, MergeResult};
+pub use crate::utils::parse_date::parse_date;
 pub use crate::utils::read_lines::read_lines;
+pub use crate::utils::similar_names::similar_names;
 pub use crate::utils::split_line::split_line;
+pub use crate::utils::validate_vocabulary::validate_vocabulary;
 
+pub mod bump_updated;
+pub mod csv_io;
 pub mod database_builder;
+pub mod database_diff;
+pub mod formatter;
 pub mod game_dispatch;
+pub mod game_tree;
 pub mod get_appid;
+pub mod git;
+pub mod history_builder;
+pub mod insert_game;
+pub mod lint;
+pub mod merge;
+pub mod parse_date;
 pub mod read_lines;
+pub mod similar_names;
 pub mod split_line;
+pub mod validate_vocabulary;
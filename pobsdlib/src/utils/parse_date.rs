@@ -0,0 +1,28 @@
+use chrono::NaiveDate;
+
+// Formats found in the database, the first one
+// being the canonical one.
+const DATE_FORMATS: [&str; 3] = ["%F", "%Y/%m/%d", "%Y.%m.%d"];
+
+/// Parse a date of the database. The canonical format
+/// is YYYY-MM-DD but YYYY/MM/DD and YYYY.MM.DD are
+/// accepted as well.
+pub fn parse_date(date: &str) -> Option<NaiveDate> {
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+}
+
+#[cfg(test)]
+mod test_parse_date {
+    use super::*;
+    #[test]
+    fn formats() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1);
+        assert_eq!(parse_date("2022-03-01"), date);
+        assert_eq!(parse_date("2022/03/01"), date);
+        assert_eq!(parse_date("2022.03.01"), date);
+        assert_eq!(parse_date("01/03/2022"), None);
+        assert_eq!(parse_date("early access"), None);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::collections::DataBase;
+
+// Number of edits needed to turn a into b.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Return the names of the games of the database close to
+/// the given name (ignoring the case), the closest first.
+/// Names differing by one edit are considered close, or by
+/// up to one edit every five characters for longer names.
+pub fn similar_names(database: &DataBase, name: &str) -> Vec<String> {
+    let name = name.to_lowercase();
+    let threshold = (name.chars().count() / 5).max(1);
+    let mut similar: Vec<(usize, String)> = database
+        .games
+        .values()
+        .filter_map(|game| {
+            let distance = levenshtein(&game.name.to_lowercase(), &name);
+            if distance <= threshold {
+                Some((distance, game.name.clone()))
+            } else {
+                None
+            }
+        })
+        .collect();
+    similar.sort();
+    similar.into_iter().map(|(_, name)| name).collect()
+}
+
+#[cfg(test)]
+mod test_similar_names {
+    use super::*;
+    use crate::DataBaseBuilder;
+    #[test]
+    fn distance() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+    #[test]
+    fn similar() {
+        let data = "Game\tThe Adventures of Shuggy\nGame\tAeternum\nGame\tAeternum II\n";
+        let database = DataBaseBuilder::verbatim().build_from_string(data.to_string());
+        assert_eq!(
+            similar_names(&database, "the adventure of shuggy"),
+            vec!["The Adventures of Shuggy".to_string()]
+        );
+        assert_eq!(
+            similar_names(&database, "Aeternum"),
+            vec!["Aeternum".to_string()]
+        );
+        assert!(similar_names(&database, "Jazz").is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use crate::collections::DataBase;
+use crate::models::{FieldKind, Game, TermReport, Vocabulary};
+
+/// Check the genres and tags of the games against the
+/// vocabulary and report the unknown and deprecated ones,
+/// with the term to use instead when one is found. Each
+/// value is checked as written in the games (rather than as
+/// the item gathering it in a normalized database). The
+/// reports are sorted by facet and value.
+pub fn validate_vocabulary(database: &DataBase, vocabulary: &Vocabulary) -> Vec<TermReport> {
+    let mut games: Vec<&Game> = database.games.values().collect();
+    games.sort_by_key(|game| game.id);
+    let mut reports: BTreeMap<(FieldKind, &str), TermReport> = BTreeMap::new();
+    for game in games {
+        for kind in [FieldKind::Genre, FieldKind::Tag] {
+            for value in kind.values(game) {
+                let report = match reports.entry((kind, value)) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => match vocabulary.check(kind, value) {
+                        Some(issue) => entry.insert(TermReport {
+                            kind,
+                            value: value.to_string(),
+                            issue,
+                            games: Vec::new(),
+                        }),
+                        None => continue,
+                    },
+                };
+                if !report.games.contains(&game.name) {
+                    report.games.push(game.name.clone());
+                }
+            }
+        }
+    }
+    reports.into_values().collect()
+}
+
+#[cfg(test)]
+mod test_validate_vocabulary {
+    use super::*;
+    use crate::models::{Normalizer, TermIssue};
+    #[test]
+    fn spelling_of_the_games() {
+        let mut database = DataBase::from_games(vec![
+            Game {
+                id: 1,
+                uuid: 1,
+                name: "Shuggy".to_string(),
+                tags: Some(vec!["indie".into()]),
+                ..Default::default()
+            },
+            Game {
+                id: 2,
+                uuid: 2,
+                name: "Kathy Rain".to_string(),
+                tags: Some(vec!["Indie".into()]),
+                ..Default::default()
+            },
+        ]);
+        database.set_normalizer(Normalizer::new());
+        let vocabulary: Vocabulary = toml::from_str("tags = [\"indie\"]\n").unwrap();
+        assert_eq!(
+            validate_vocabulary(&database, &vocabulary),
+            vec![TermReport {
+                kind: FieldKind::Tag,
+                value: "Indie".to_string(),
+                issue: TermIssue::Unknown {
+                    suggestion: Some("indie".to_string())
+                },
+                games: vec!["Kathy Rain".to_string()],
+            }]
+        );
+    }
+}
@@ -1,5 +1,17 @@
 extern crate pobsdlib;
-use pobsdlib::{DataBaseBuilder, GameFilter};
+extern crate toml;
+use pobsdlib::collections::source_hash;
+use pobsdlib::record::{self, GameRecord};
+use pobsdlib::utils::{
+    format_database, read_game_tree, read_games, validate_vocabulary, write_game_tree, write_games,
+    CsvOptions,
+};
+use pobsdlib::{
+    BorrowedDataBase, ChangeKind, DataBase, DataBaseBuilder, FieldKind, Game, GameFilter,
+    HistoryBuilder, Normalizer, Vocabulary,
+};
+use std::fs;
+use std::process::Command;
 
 #[test]
 fn test_get_all_games() {
@@ -173,3 +185,331 @@ fn test_game_contains_or() {
     // check we have the right number
     assert_eq!(games.count, 0);
 }
+#[test]
+fn test_history() {
+    let repo = std::env::temp_dir().join(format!("pobsdlib-test-history-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&repo);
+    fs::create_dir_all(&repo).unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args([
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=tester@example.org",
+            ])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(&["init", "-q"]);
+    fs::write(repo.join("openbsd-games.db"), "Game\tfirst\nEngine\tXNA\n").unwrap();
+    git(&["add", "openbsd-games.db"]);
+    git(&["commit", "-q", "-m", "add first"]);
+    fs::write(
+        repo.join("openbsd-games.db"),
+        "Game\tfirst\nEngine\tFNA\nGame\tsecond\n",
+    )
+    .unwrap();
+    git(&["commit", "-q", "-a", "-m", "update first, add second"]);
+    let history = HistoryBuilder::new(&repo).build().unwrap();
+    assert_eq!(history.get_all_commits().len(), 2);
+    let db = DataBaseBuilder::new(false, false)
+        .build_from_file(repo.join("openbsd-games.db").to_str().unwrap());
+    let first = &db.get_game_by_name("first").items[0];
+    let events = history.get_game_history(first.uuid);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].change.kind, ChangeKind::Added);
+    assert_eq!(events[0].commit.author, "Tester");
+    match &events[1].change.kind {
+        ChangeKind::Modified(fields) => {
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].field, "Engine");
+            assert_eq!(fields[0].new.as_ref().unwrap(), "FNA");
+        }
+        _ => panic!("Game should have been modified"),
+    }
+    let today = chrono::Local::now().date_naive();
+    assert_eq!(history.get_database_as_of(today).unwrap(), db);
+    assert!(history
+        .get_database_as_of(today - chrono::Duration::days(2))
+        .is_none());
+    let latest = history.get_latest_events(1);
+    assert_eq!(latest[0].change.name, "second");
+    assert_eq!(latest[0].change.kind, ChangeKind::Added);
+    fs::remove_dir_all(&repo).unwrap();
+}
+
+#[test]
+fn test_json_round_trip() {
+    let mut db = DataBaseBuilder::new(false, false).build_from_file("tests/data/test-games.db");
+    let json = db.to_json();
+    assert_eq!(DataBase::from_json(&json).unwrap(), db);
+    db.register_facet(FieldKind::Status);
+    let from_json = DataBase::from_json(&db.to_json()).unwrap();
+    assert!(from_json.facets().contains(&FieldKind::Status));
+    assert_eq!(from_json, db);
+    assert!(DataBase::from_json("{\"games\": 1}").is_err());
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let db = DataBaseBuilder::new(false, false).build_from_file("tests/data/test-games.db");
+    let games = db.get_all_games().items;
+    let mut csv: Vec<u8> = Vec::new();
+    write_games(&games, &CsvOptions::csv(), &mut csv).unwrap();
+    assert_eq!(
+        read_games(csv.as_slice(), &CsvOptions::csv()).unwrap(),
+        games
+    );
+}
+
+#[test]
+fn test_json_schema_is_up_to_date() {
+    // regenerate with: pobsd schema > pobsdlib/schema/database.schema.json
+    let schema = fs::read_to_string("schema/database.schema.json").unwrap();
+    assert_eq!(schema.trim_end(), DataBase::json_schema());
+}
+
+#[test]
+fn test_game_tree_round_trip() {
+    let dir = std::env::temp_dir().join(format!("pobsdlib-tree-{}", std::process::id()));
+    let data = fs::read_to_string("../db/openbsd-games.db").unwrap();
+    let db = DataBaseBuilder::verbatim().build_from_string(data.clone());
+    write_game_tree(&db, &dir).unwrap();
+    // written twice to check that the tree is replaced
+    write_game_tree(&db, &dir).unwrap();
+    let tree = read_game_tree(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(tree, db);
+    let mut content = String::new();
+    for game in tree.get_all_games().items {
+        content.push_str(&format!("{}\n", game));
+    }
+    assert_eq!(content, format_database(&data).unwrap());
+}
+
+#[test]
+fn test_record_format() {
+    let data = fs::read_to_string("tests/data/test-games.db").unwrap();
+    let records: Vec<GameRecord> = record::from_str(&data).unwrap();
+    let games: Vec<_> = records
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(index, record)| record.into_game(index + 1))
+        .collect();
+    let db = DataBaseBuilder::verbatim().build_from_string(data.clone());
+    assert_eq!(DataBase::from_games(games), db);
+    assert_eq!(
+        record::to_string(&records).unwrap(),
+        format_database(&data).unwrap()
+    );
+}
+
+#[test]
+fn test_database_cache() {
+    let cache = std::env::temp_dir().join(format!("pobsdlib-cache-{}", std::process::id()));
+    let data = fs::read_to_string("tests/data/test-games.db").unwrap();
+    let db = DataBaseBuilder::new(true, true).build_from_string(data.clone());
+    let built = DataBaseBuilder::new(true, true).build_from_string_cached(data.clone(), &cache);
+    assert_eq!(built, db);
+    // the cache is used for the same data and settings only
+    assert!(DataBase::load_cache(&cache, source_hash(data.as_bytes())).is_err());
+    let cached = DataBaseBuilder::new(true, true).build_from_string_cached(data.clone(), &cache);
+    assert_eq!(cached, db);
+    let verbatim = DataBaseBuilder::verbatim().build_from_string_cached(data.clone(), &cache);
+    assert_eq!(
+        verbatim,
+        DataBaseBuilder::verbatim().build_from_string(data)
+    );
+    fs::remove_file(&cache).unwrap();
+}
+
+#[test]
+fn test_borrowed_database() {
+    let data = fs::read_to_string("tests/data/test-games.db").unwrap();
+    let db = BorrowedDataBase::parse(&data);
+    assert_eq!(db.get_all_games().count, 8);
+    assert_eq!(
+        db.to_database(),
+        DataBaseBuilder::verbatim().build_from_string(data.clone())
+    );
+}
+
+#[test]
+fn test_shared_symbols() {
+    let db = DataBaseBuilder::new(true, true).build_from_file("tests/data/test-games.db");
+    let from_games = DataBase::from_games(db.get_all_games().items);
+    for db in [db, from_games] {
+        let tags = db.get_all_tags().items;
+        for game in db.get_all_games().items {
+            for tag in game.tags.iter().flatten() {
+                let item = tags.iter().find(|item| item.name == *tag).unwrap();
+                assert!(tag.ptr_eq(&item.name));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mutable_database() {
+    let mut db = DataBaseBuilder::new(true, true).build_from_file("tests/data/test-games.db");
+    let mut game = db.get_game_by_name("Aeternum").items.remove(0);
+    let uuid = game.uuid;
+    // update
+    game.engine = Some("test-engine".into());
+    game.tags = Some(vec!["test-tag".into(), "test-tag".into()]);
+    assert!(db.update_game(game.clone()).is_some());
+    assert_eq!(
+        db.get_game_by_engine("test-engine").items,
+        vec![game.clone()]
+    );
+    assert_eq!(db.get_game_by_tag("test-tag").count, 1);
+    assert!(db
+        .get_all_engines()
+        .items
+        .iter()
+        .all(|item| !item.games.is_empty()));
+    // rename
+    let renamed = db.rename_game(uuid, "Aeternum 2").unwrap();
+    assert!(db.get_game_by_id(uuid).is_none());
+    assert_eq!(db.get_game_by_engine("test-engine").items[0].uuid, renamed);
+    assert!(db.rename_game(renamed, "Aedemphia").is_none());
+    // remove
+    let removed = db.remove_game(renamed).unwrap();
+    assert_eq!(removed.name, "Aeternum 2");
+    assert_eq!(db.get_game_by_engine("test-engine").count, 0);
+    assert!(db
+        .get_all_engines()
+        .items
+        .iter()
+        .all(|item| item.name != "test-engine"));
+    assert_eq!(db.get_all_games().count, 7);
+    // insert
+    assert!(db.insert_game(removed.clone()).is_none());
+    assert_eq!(db.get_game_by_tag("test-tag").count, 1);
+    assert!(db
+        .update_game(Game {
+            name: "Unknown".to_string(),
+            ..Default::default()
+        })
+        .is_none());
+}
+
+#[test]
+fn test_set_based_items() {
+    let data = "Game\tShuggy\nEngine\tFNA\nEngine\tFNA\nTags\tindie, indie\nGame\tKathy Rain\nTags\tindie\n";
+    let db = DataBaseBuilder::verbatim().build_from_string(data.to_string());
+    assert_eq!(db.get_game_by_tag("indie").count, 2);
+    assert_eq!(db.get_game_by_engine("FNA").count, 1);
+    let tags = db.get_all_tags().items;
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].count(), 2);
+    let mut uuids = vec![
+        Game::compute_uuid("Shuggy"),
+        Game::compute_uuid("Kathy Rain"),
+    ];
+    uuids.sort();
+    assert_eq!(tags[0].games.iter().copied().collect::<Vec<u64>>(), uuids);
+}
+
+#[test]
+fn test_facets() {
+    let mut db = DataBaseBuilder::new(true, true).build_from_file("tests/data/test-games.db");
+    assert_eq!(
+        db.games_by(FieldKind::Engine, "FNA").items,
+        db.get_game_by_engine("FNA").items
+    );
+    assert_eq!(db.items(FieldKind::Tag).count, db.get_all_tags().count);
+    assert_eq!(db.facets(), FieldKind::DEFAULT.to_vec());
+    // derived facets are only indexed once registered
+    assert_eq!(db.games_by(FieldKind::Setup, "fnaify").count, 0);
+    db.register_facet(FieldKind::Store);
+    db.register_facet(FieldKind::Status);
+    db.register_facet(FieldKind::Setup);
+    assert_eq!(db.facets(), FieldKind::ALL.to_vec());
+    assert_eq!(db.games_by(FieldKind::Store, "Steam").count, 5);
+    assert_eq!(db.games_by(FieldKind::Setup, "fnaify").count, 3);
+    assert_eq!(
+        db.games_by(FieldKind::Status, "runs").items[0].name,
+        "Aeternum"
+    );
+    // registered facets follow the changes of the database
+    let uuid = db.games_by(FieldKind::Status, "runs").items[0].uuid;
+    let game = db.remove_game(uuid).unwrap();
+    assert_eq!(db.games_by(FieldKind::Store, "Steam").count, 4);
+    assert_eq!(db.games_by(FieldKind::Status, "runs").count, 0);
+    db.insert_game(game);
+    assert_eq!(db.games_by(FieldKind::Store, "Steam").count, 5);
+}
+
+#[test]
+fn test_normalizer() {
+    let data = "Game\tShuggy\nTags\tPoint and Click, indie\nDev\tSmudged Cat Games Ltd.\nGame\tKathy Rain\nTags\tpoint-and-click\nDev\tClifftop Games\nGame\tAeternum\nTags\tpnc\nDev\tSmudged Cat Games\n";
+    let mut db = DataBaseBuilder::verbatim().build_from_string(data.to_string());
+    assert_eq!(db.get_game_by_tag("point and click").count, 0);
+    let mut normalizer = Normalizer::new();
+    normalizer.add_alias(FieldKind::Tag, "pnc", "point and click");
+    db.set_normalizer(normalizer);
+    assert_eq!(db.get_game_by_tag("POINT AND CLICK").count, 3);
+    assert_eq!(db.get_game_by_dev("smudged cat games").count, 2);
+    // the items are named after the first spelling
+    let tags = db.get_all_tags();
+    assert_eq!(tags.count, 2);
+    let item = tags.items.iter().find(|item| item.count() == 3).unwrap();
+    assert_eq!(item.name, "Point and Click");
+    // the games keep their spelling
+    let game = db.get_game_by_name("Kathy Rain").items.remove(0);
+    assert_eq!(game.tags, Some(vec!["point-and-click".into()]));
+    // changes go through the normalizer as well
+    db.remove_game(game.uuid);
+    assert_eq!(db.get_game_by_tag("pnc").count, 2);
+}
+
+#[test]
+fn test_validate_vocabulary() {
+    let db = DataBaseBuilder::new(true, true).build_from_file("tests/data/test-games.db");
+    let vocabulary: Vocabulary = toml::from_str(
+        "[tags.indie]\n[tags.anime]\ndescription = \"Japanese animation\"\ndeprecated = [\"manga\"]\n[tags.\"bullet hell\"]\n[tags.gratis]\ndeprecated = [\"free\"]\n",
+    )
+    .unwrap();
+    let reports = validate_vocabulary(&db, &vocabulary);
+    let summary: Vec<(&str, String, Vec<String>)> = reports
+        .iter()
+        .map(|report| {
+            (
+                report.value.as_str(),
+                report.issue.to_string(),
+                report.games.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "bullethell",
+                "unknown (did you mean bullet hell?)".to_string(),
+                vec!["Aeternum".to_string()]
+            ),
+            (
+                "free",
+                "deprecated (use gratis instead)".to_string(),
+                vec!["Aedemphia".to_string()]
+            ),
+            (
+                "manga",
+                "deprecated (use anime instead)".to_string(),
+                vec!["Aeternum".to_string()]
+            ),
+        ]
+    );
+    assert_eq!(
+        vocabulary.description(FieldKind::Tag, "anime"),
+        Some("Japanese animation")
+    );
+}
@@ -5,22 +5,57 @@ pub mod wrappers;
 
 use axum::{extract::Extension, routing::get, Router};
 
+use std::env;
+use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 
 use crate::routes::{game_details, game_list, rss};
-use pobsdlib::{DataBase, DataBaseBuilder};
+use crate::wrappers::SnapshotCache;
+use pobsdlib::{DataBase, DataBaseBuilder, History, HistoryBuilder, Normalizer, Vocabulary};
+
+// Build the database from its content, through the binary
+// cache given by POBSDRS_CACHE if any: the cache is reused as
+// long as the content does not change, and rebuilt otherwise.
+// The facets are then normalized with the alias table given by
+// POBSDRS_ALIASES if any.
+fn build_database(content: String) -> DataBase {
+    let builder = DataBaseBuilder::new(true, true);
+    let mut database = match env::var("POBSDRS_CACHE") {
+        Ok(cache) => builder.build_from_string_cached(content, Path::new(&cache)),
+        Err(_) => builder.build_from_string(content),
+    };
+    if let Ok(aliases) = env::var("POBSDRS_ALIASES") {
+        match Normalizer::from_file(&aliases) {
+            Ok(normalizer) => database.set_normalizer(normalizer),
+            Err(err) => panic!("Could not read the aliases {}: {}", aliases, err),
+        }
+    }
+    database
+}
 
 #[tokio::main]
 async fn main() {
     let shared_db: Arc<DataBase>;
-    if let Ok(req) = reqwest::get(
+    let shared_history: Arc<Option<History>>;
+    // When a local clone of the database repository is given,
+    // the database is read from it and its history is loaded.
+    if let Ok(repo) = env::var("POBSDRS_REPO") {
+        let path = Path::new(&repo).join("openbsd-games.db");
+        let content = fs::read_to_string(path).expect("Could not read the database");
+        shared_db = Arc::new(build_database(content));
+        match HistoryBuilder::new(&repo).build() {
+            Ok(history) => shared_history = Arc::new(Some(history)),
+            Err(err) => panic!("Could not load the history of the database: {}", err),
+        }
+    } else if let Ok(req) = reqwest::get(
         "https://raw.githubusercontent.com/playonbsd/OpenBSD-Games-Database/main/openbsd-games.db",
     )
     .await
     {
         if let Ok(content) = req.text().await {
-            let db = DataBaseBuilder::new(true, true).build_from_string(content);
-            shared_db = Arc::new(db);
+            shared_db = Arc::new(build_database(content));
+            shared_history = Arc::new(None);
         } else {
             panic!("Could no fetch the database from GitHub");
         }
@@ -28,14 +63,28 @@ async fn main() {
         panic!("Could no fetch the database from GitHub");
     }
 
+    // the vocabulary given by POBSDRS_VOCABULARY describes the tags
+    // and genres on their pages
+    let vocabulary = match env::var("POBSDRS_VOCABULARY") {
+        Ok(path) => match Vocabulary::from_file(&path) {
+            Ok(vocabulary) => vocabulary,
+            Err(err) => panic!("Could not read the vocabulary {}: {}", path, err),
+        },
+        Err(_) => Vocabulary::new(),
+    };
+
     let app = Router::new()
         .route(
             "/",
             get(game_list::game_list).post(game_list::game_list_search),
         )
         .route("/:game_id", get(game_details::game_details))
+        .route("/:game_id/history", get(game_details::game_history))
         .route("/rss", get(rss::rss))
-        .layer(Extension(shared_db));
+        .layer(Extension(shared_db))
+        .layer(Extension(shared_history))
+        .layer(Extension(Arc::new(SnapshotCache::new())))
+        .layer(Extension(Arc::new(vocabulary)));
 
     // run it with hyper on localhost:3000
     axum::Server::bind(&"127.0.0.1:3000".parse().unwrap())
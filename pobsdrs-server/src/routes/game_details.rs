@@ -1,22 +1,94 @@
 use crate::models::HtmlTemplate;
+use crate::wrappers::SnapshotCache;
 use askama::Template;
-use axum::extract::{Extension, Path};
+use axum::extract::{Extension, Path, Query};
 use axum::response::IntoResponse;
-use pobsdlib::{DataBase, Game};
+use pobsdlib::{ChangeKind, DataBase, Game, History};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Template)]
 #[template(path = "game_details.html")]
 struct GameDetailsTemplate {
     game: Option<Game>,
+    history_on: bool,
+    as_of: Option<String>,
+}
+
+pub struct HistoryEntry {
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+    pub summary: String,
+    pub changes: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "game_history.html")]
+struct GameHistoryTemplate {
+    game: Option<Game>,
+    history_on: bool,
+    entries: Vec<HistoryEntry>,
 }
 
 pub async fn game_details(
     Extension(db): Extension<Arc<DataBase>>,
+    Extension(history): Extension<Arc<Option<History>>>,
+    Extension(snapshots): Extension<Arc<SnapshotCache>>,
     Path(game_id): Path<u64>,
+    Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
+    let old_db = snapshots.database_as_of(&history, params.get("as_of"));
+    let as_of = old_db.as_ref().and(params.get("as_of").cloned());
+    let db: &DataBase = old_db.as_deref().unwrap_or(&db);
     let template = GameDetailsTemplate {
         game: db.get_game_by_id(game_id),
+        history_on: history.is_some(),
+        as_of,
+    };
+    HtmlTemplate(template)
+}
+
+pub async fn game_history(
+    Extension(db): Extension<Arc<DataBase>>,
+    Extension(history): Extension<Arc<Option<History>>>,
+    Path(game_id): Path<u64>,
+) -> impl IntoResponse {
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+    if let Some(history) = &*history {
+        // newest first
+        for event in history.get_game_history(game_id).into_iter().rev() {
+            let (summary, changes) = match event.change.kind {
+                ChangeKind::Added => ("Added".to_string(), Vec::new()),
+                ChangeKind::Removed => ("Removed".to_string(), Vec::new()),
+                ChangeKind::Modified(fields) => (
+                    "Modified".to_string(),
+                    fields
+                        .into_iter()
+                        .map(|field| {
+                            format!(
+                                "{}: {} → {}",
+                                field.field,
+                                field.old.unwrap_or_else(|| "(empty)".to_string()),
+                                field.new.unwrap_or_else(|| "(empty)".to_string())
+                            )
+                        })
+                        .collect(),
+                ),
+            };
+            entries.push(HistoryEntry {
+                commit: event.commit.id,
+                author: event.commit.author,
+                date: event.commit.date.format("%F").to_string(),
+                summary,
+                changes,
+            });
+        }
+    }
+    let template = GameHistoryTemplate {
+        game: db.get_game_by_id(game_id),
+        history_on: history.is_some(),
+        entries,
     };
     HtmlTemplate(template)
 }
@@ -1,8 +1,8 @@
-use crate::views::game_list::game_list_view;
-use crate::wrappers::GameFilterWrapper;
+use crate::views::game_list::{game_list_view, TermDescription};
+use crate::wrappers::{GameFilterWrapper, SnapshotCache};
 use axum::extract::{Extension, Form, Query};
 use axum::response::IntoResponse;
-use pobsdlib::{DataBase, Game, QueryResult};
+use pobsdlib::{DataBase, FieldKind, Game, History, QueryResult, Vocabulary};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -14,16 +14,40 @@ pub struct Search {
 
 pub async fn game_list(
     Extension(db): Extension<Arc<DataBase>>,
+    Extension(history): Extension<Arc<Option<History>>>,
+    Extension(snapshots): Extension<Arc<SnapshotCache>>,
+    Extension(vocabulary): Extension<Arc<Vocabulary>>,
     Query(params): Query<HashMap<String, String>>,
 ) -> impl IntoResponse {
     let game_filter_wrapper = GameFilterWrapper::new(&params);
+    let old_db = snapshots.database_as_of(&history, params.get("as_of"));
+    let as_of = old_db.as_ref().and(params.get("as_of").cloned());
+    let db: &DataBase = old_db.as_ref().unwrap_or(&db);
     let game_query: QueryResult<Game> = if game_filter_wrapper.filter_on {
         db.game_contains_or(game_filter_wrapper.game_filter)
     } else {
         db.get_all_games()
     };
+    let mut query_str = game_filter_wrapper.query_str;
+    if let Some(as_of) = &as_of {
+        if !query_str.is_empty() {
+            query_str.push('&');
+        }
+        query_str.push_str(&format!("as_of={}", as_of));
+    }
+    // the page of a tag (or genre) shows its description
+    let term = [("tag", FieldKind::Tag), ("genre", FieldKind::Genre)]
+        .iter()
+        .find_map(|(param, kind)| {
+            let name = params.get(*param)?;
+            let description = vocabulary.description(*kind, name)?;
+            Some(TermDescription {
+                name: name.clone(),
+                description: description.to_string(),
+            })
+        });
     let page = params.get("page");
-    game_list_view(game_query, page.cloned(), game_filter_wrapper.query_str)
+    game_list_view(game_query, page.cloned(), query_str, as_of, term)
 }
 
 pub async fn game_list_search(
@@ -48,5 +72,5 @@ pub async fn game_list_search(
     } else {
         db.get_all_games()
     };
-    game_list_view(game_query, None, game_filter_wrapper.query_str)
+    game_list_view(game_query, None, game_filter_wrapper.query_str, None, None)
 }
@@ -3,18 +3,22 @@ use axum::extract::Extension;
 use axum::http::{header, HeaderMap};
 use axum::response::IntoResponse;
 use chrono::{prelude::*, Duration};
-use pobsdlib::{DataBase, Game};
+use pobsdlib::{ChangeKind, DataBase, Game, History};
 use rss::{ChannelBuilder, Item};
 use std::sync::Arc;
 
+// Upper bound on the events read from the history to fill the feed.
+const MAX_HISTORY_EVENTS: usize = 500;
+
 #[derive(Template)]
 #[template(path = "rss.html")]
 struct RSSTemplate {
     game: Option<Game>,
 }
 
-pub async fn rss(Extension(db): Extension<Arc<DataBase>>) -> impl IntoResponse {
-    let now = Local::now().naive_local().date();
+// Guess the updates from the Added and Updated fields
+// when the history of the database is not available.
+fn items_from_games(db: &DataBase, now: NaiveDate) -> Vec<Item> {
     let game_query = db.get_all_games();
     let mut games: Vec<Game> = game_query
         .items
@@ -42,6 +46,56 @@ pub async fn rss(Extension(db): Extension<Arc<DataBase>>) -> impl IntoResponse {
         }
         items.push(item);
     }
+    items
+}
+
+// Use the changes recorded in the history of the database.
+fn items_from_history(db: &DataBase, history: &History, now: NaiveDate) -> Vec<Item> {
+    let mut items: Vec<Item> = Vec::new();
+    for event in history.get_latest_events(MAX_HISTORY_EVENTS) {
+        if now - event.commit.date.date_naive() >= Duration::days(90) {
+            break;
+        }
+        let mut item = Item::default();
+        let name = &event.change.name;
+        match &event.change.kind {
+            ChangeKind::Added => item.set_title(format!("The game {} has been added.", name)),
+            ChangeKind::Removed => item.set_title(format!("The game {} has been removed.", name)),
+            ChangeKind::Modified(fields) => {
+                let fields: Vec<&str> = fields.iter().map(|field| field.field.as_str()).collect();
+                item.set_title(format!(
+                    "The game {} has been updated ({}).",
+                    name,
+                    fields.join(", ")
+                ))
+            }
+        }
+        item.set_author(event.commit.author.clone());
+        item.set_pub_date(event.commit.date.to_rfc2822());
+        item.set_link(format!(
+            "https://pobsd.chocolatines.org/{}/history",
+            event.change.uuid
+        ));
+        let template = RSSTemplate {
+            game: db.get_game_by_id(event.change.uuid),
+        };
+        if let Ok(content) = template.render() {
+            item.set_content(content);
+        }
+        items.push(item);
+    }
+    items
+}
+
+pub async fn rss(
+    Extension(db): Extension<Arc<DataBase>>,
+    Extension(history): Extension<Arc<Option<History>>>,
+) -> impl IntoResponse {
+    let now = Local::now().naive_local().date();
+    let items = match &*history {
+        Some(history) => items_from_history(&db, history, now),
+        None => items_from_games(&db, now),
+    };
     let channel = ChannelBuilder::default()
         .title("PlayOnBSD updates")
         .link("https://playonbsd.com")
@@ -4,18 +4,29 @@ use askama::Template;
 use axum::response::IntoResponse;
 use pobsdlib::{Game, QueryResult};
 
+/// Description of the tag (or genre) the games are
+/// listed for, taken from the vocabulary.
+pub struct TermDescription {
+    pub name: String,
+    pub description: String,
+}
+
 #[derive(Template)]
 #[template(path = "game_list.html")]
 struct GameListTemplate {
     games: Vec<Game>,
     query_str: String,
     paginator: Page,
+    as_of: Option<String>,
+    term: Option<TermDescription>,
 }
 
 pub fn game_list_view(
     game_query: QueryResult<Game>,
     page: Option<String>,
     query_str: String,
+    as_of: Option<String>,
+    term: Option<TermDescription>,
 ) -> impl IntoResponse {
     let page = match page {
         Some(page) => page.parse::<usize>().unwrap(),
@@ -27,6 +38,8 @@ pub fn game_list_view(
             games: game_query.items[page.first_element..=page.last_element].to_vec(),
             query_str,
             paginator: page,
+            as_of,
+            term,
         },
         None => {
             let page = Page {
@@ -39,6 +52,8 @@ pub fn game_list_view(
                 games: game_query.items,
                 query_str: "".to_string(),
                 paginator: page,
+                as_of,
+                term,
             }
         }
     };
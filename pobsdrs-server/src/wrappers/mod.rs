@@ -1,6 +1,8 @@
 pub mod game_filter_wrapper;
 pub mod paginator;
+pub mod snapshot_cache;
 
 pub use game_filter_wrapper::GameFilterWrapper;
 pub use paginator::Page;
 pub use paginator::Paginator;
+pub use snapshot_cache::SnapshotCache;
@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use pobsdlib::{DataBase, History};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+// Number of databases kept by the cache.
+const MAX_CACHED_SNAPSHOTS: usize = 8;
+
+/// Keep the databases rebuilt from the history for the
+/// most recently requested commits, so that browsing the
+/// database as of a given date does not rebuild it on every
+/// request.
+#[derive(Default)]
+pub struct SnapshotCache {
+    // commit id and database, the most recently used last
+    databases: Mutex<VecDeque<(String, Arc<DataBase>)>>,
+}
+
+impl SnapshotCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Return the database as it was at the given date
+    /// (formatted as YYYY-MM-DD) when the history of the
+    /// database is available.
+    pub fn database_as_of(
+        &self,
+        history: &Option<History>,
+        as_of: Option<&String>,
+    ) -> Option<Arc<DataBase>> {
+        let history = history.as_ref()?;
+        let date = NaiveDate::parse_from_str(as_of?, "%F").ok()?;
+        let snapshot = match history.get_snapshots().get_snapshot_as_of(date) {
+            Some(snapshot) => snapshot,
+            // the database did not exist at that date
+            None => return Some(Arc::new(DataBase::default())),
+        };
+        let id = &snapshot.commit.id;
+        if let Some(database) = self.get(id) {
+            return Some(database);
+        }
+        // rebuilt without holding the lock, so that other
+        // requests are not blocked meanwhile
+        let database = Arc::new(snapshot.to_database());
+        let mut databases = self.databases.lock().unwrap();
+        if !databases.iter().any(|(commit, _)| commit == id) {
+            if databases.len() == MAX_CACHED_SNAPSHOTS {
+                databases.pop_front();
+            }
+            databases.push_back((id.clone(), database.clone()));
+        }
+        Some(database)
+    }
+    // Return the cached database of the commit, marking it as
+    // the most recently used.
+    fn get(&self, id: &str) -> Option<Arc<DataBase>> {
+        let mut databases = self.databases.lock().unwrap();
+        let position = databases.iter().position(|(commit, _)| commit == id)?;
+        let entry = databases.remove(position)?;
+        let database = entry.1.clone();
+        databases.push_back(entry);
+        Some(database)
+    }
+}